@@ -52,6 +52,18 @@ pub struct Camera3d {
     ///
     /// **Note:** You can get better-looking results at any quality level by enabling TAA. See: [`TemporalAntiAliasPlugin`](crate::experimental::taa::TemporalAntiAliasPlugin).
     pub screen_space_specular_transmission_quality: ScreenSpaceTransmissionQuality,
+    /// How the screen space specular transmission blur effect is computed. See [`ScreenSpaceTransmissionBlur`].
+    pub screen_space_specular_transmission_blur: ScreenSpaceTransmissionBlur,
+    /// The resolution of the [`ViewTransmissionTexture`](crate::core_3d::ViewTransmissionTexture), relative
+    /// to the camera's physical target size. Clamped to the `(0.0, 1.0]` range. Defaults to `1.0`.
+    ///
+    /// Lower values (e.g. `0.5` or `0.25`) make each step of the [`Transmissive3d`](crate::core_3d::Transmissive3d)
+    /// pass cheaper, at the cost of a blurrier background behind transmissive objects. The texture is then
+    /// downsampled from the main texture instead of copied, and bilinearly upsampled when read. The full
+    /// resolution is used until the pipeline downsampling it is ready.
+    ///
+    /// **Note:** Scales below `1.0` are not supported on WebGL 2, where the full resolution is always used.
+    pub transmission_texture_scale: f32,
 }
 
 impl Default for Camera3d {
@@ -61,6 +73,8 @@ impl Default for Camera3d {
             depth_texture_usages: TextureUsages::RENDER_ATTACHMENT.into(),
             screen_space_specular_transmission_steps: 1,
             screen_space_specular_transmission_quality: Default::default(),
+            screen_space_specular_transmission_blur: Default::default(),
+            transmission_texture_scale: 1.0,
         }
     }
 }
@@ -135,6 +149,42 @@ pub enum ScreenSpaceTransmissionQuality {
     ///
     /// `num_taps` = 32
     Ultra,
+
+    /// Explicit blur kernel parameters, for finer control than the presets above.
+    ///
+    /// The presets correspond to `spiral_turns` = 1 and `fractional_jitter` = 1.0.
+    Custom {
+        /// The number of taps. Clamped to the `1..=64` range.
+        taps: u32,
+        /// How many full turns the rotation of consecutive spirals (of 8 taps each) completes.
+        /// Clamped to the `1..=8` range.
+        spiral_turns: u32,
+        /// How much of a full turn the per-pixel random rotation of the spirals covers, from `0.0`
+        /// (no jitter) to `1.0`. Quantized to steps of `1/15`.
+        fractional_jitter: f32,
+    },
+}
+
+/// How the screen space transmission blur effect, applied to whatever's “behind” transmissive
+/// objects when their `roughness` is greater than `0.0`, is computed.
+#[derive(Default, Clone, Copy, Reflect, PartialEq, Eq, Hash, Debug)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum ScreenSpaceTransmissionBlur {
+    /// Takes multiple jittered taps from the transmission texture, in a spiral pattern.
+    ///
+    /// The number of taps is controlled by [`ScreenSpaceTransmissionQuality`]. The jitter is
+    /// noisy under camera motion unless TAA is enabled.
+    #[default]
+    Jittered,
+
+    /// Generates a blurred mip chain of the transmission texture after each copy, and takes a
+    /// single sample from the mip level matching the material's roughness.
+    ///
+    /// Stable under camera motion even without TAA, at the cost of a few downsampling passes per
+    /// [`Camera3d::screen_space_specular_transmission_steps`].
+    ///
+    /// **Note:** Not supported on WebGL 2, where this falls back to [`ScreenSpaceTransmissionBlur::Jittered`].
+    MipChain,
 }
 
 /// The camera coordinate space is right-handed x-right, y-up, z-back.
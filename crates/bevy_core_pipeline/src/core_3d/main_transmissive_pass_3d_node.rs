@@ -1,11 +1,16 @@
-use super::{Camera3d, ViewTransmissionTexture};
+use super::{
+    blur_transmission_texture, downsample_to_transmission_texture, Camera3d,
+    TransmissionBlurPipeline, TransmissionBlurPipelineId, TransmissionDownsamplePipelineId,
+    ViewTransmissionBlurBindGroups, ViewTransmissionTexture,
+};
 use crate::core_3d::Transmissive3d;
 use bevy_ecs::{prelude::*, query::QueryItem};
+use bevy_math::Rect;
 use bevy_render::{
     camera::ExtractedCamera,
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_phase::ViewSortedRenderPhases,
-    render_resource::{Extent3d, RenderPassDescriptor, StoreOp},
+    render_resource::{Extent3d, PipelineCache, RenderPassDescriptor, StoreOp},
     renderer::RenderContext,
     view::{ViewDepthTexture, ViewTarget},
 };
@@ -26,13 +31,26 @@ impl ViewNode for MainTransmissivePass3dNode {
         &'static ViewTarget,
         Option<&'static ViewTransmissionTexture>,
         &'static ViewDepthTexture,
+        Option<(
+            &'static TransmissionBlurPipelineId,
+            &'static ViewTransmissionBlurBindGroups,
+        )>,
+        Option<&'static TransmissionDownsamplePipelineId>,
     );
 
     fn run(
         &self,
         graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (camera, camera_3d, target, transmission, depth): QueryItem<Self::ViewQuery>,
+        (
+            camera,
+            camera_3d,
+            target,
+            transmission,
+            depth,
+            transmission_blur,
+            downsample_pipeline_id,
+        ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let view_entity = graph.view_entity();
@@ -69,27 +87,63 @@ impl ViewNode for MainTransmissivePass3dNode {
                 let transmission =
                     transmission.expect("`ViewTransmissionTexture` should exist at this point");
 
-                // `transmissive_phase.items` are depth sorted, so we split them into N = `screen_space_specular_transmission_steps`
+                // See `Camera3d::transmission_texture_scale`. Only scaled down once the downsampling pipeline is
+                // ready, see `prepare_core_3d_transmission_textures`
+                let scaled = transmission.texture.width() != physical_target_size.x
+                    || transmission.texture.height() != physical_target_size.y;
+
+                // `transmissive_phase.items` are depth sorted, so we split them into at most N = `screen_space_specular_transmission_steps`
                 // ranges, rendering them back-to-front in multiple steps, allowing multiple levels of transparency.
+                // Materials can override N, in which case consecutive items with the same N are split separately, and items
+                // seen through (i.e. behind and overlapping) an item with a higher N use that N as well.
                 //
-                // Note: For the sake of simplicity, we currently split items evenly among steps. In the future, we
-                // might want to use a more sophisticated heuristic (e.g. based on view bounds, or with an exponential
-                // falloff so that nearby objects have more levels of transparency available to them)
-                for range in split_range(
-                    0..transmissive_phase.items.len(),
+                // Note: A new step is only started when an item overlaps (in screen space, including the area it reads
+                // through refraction and blur) an item of the current step, so items that don't overlap share a step,
+                // saving texture copies. If the screen space bounds of some items are unknown, we fall back to splitting
+                // items evenly among steps.
+                for (range, copy) in transmissive_steps(
+                    &transmissive_phase.items,
                     screen_space_specular_transmission_steps,
                 ) {
                     // Copy the main texture to the transmission texture, allowing to use the color output of the
                     // previous step (or of the `Opaque3d` phase, for the first step) as a transmissive color input
-                    render_context.command_encoder().copy_texture_to_texture(
-                        target.main_texture().as_image_copy(),
-                        transmission.texture.as_image_copy(),
-                        Extent3d {
-                            width: physical_target_size.x,
-                            height: physical_target_size.y,
-                            depth_or_array_layers: 1,
-                        },
-                    );
+                    if copy {
+                        if scaled {
+                            // A scaled down transmission texture can't be copied into, so downsample instead
+                            if let (Some(pipeline_id), Some((_, bind_groups))) =
+                                (downsample_pipeline_id, transmission_blur)
+                            {
+                                downsample_to_transmission_texture(
+                                    render_context,
+                                    world.resource::<PipelineCache>(),
+                                    world.resource::<TransmissionBlurPipeline>(),
+                                    pipeline_id,
+                                    bind_groups,
+                                    target.main_texture_view(),
+                                );
+                            }
+                        } else {
+                            render_context.command_encoder().copy_texture_to_texture(
+                                target.main_texture().as_image_copy(),
+                                transmission.texture.as_image_copy(),
+                                Extent3d {
+                                    width: physical_target_size.x,
+                                    height: physical_target_size.y,
+                                    depth_or_array_layers: 1,
+                                },
+                            );
+                        }
+
+                        // Generate the blurred mip chain from the freshly copied texture
+                        if let Some((pipeline_id, bind_groups)) = transmission_blur {
+                            blur_transmission_texture(
+                                render_context,
+                                world.resource::<PipelineCache>(),
+                                pipeline_id,
+                                bind_groups,
+                            );
+                        }
+                    }
 
                     let mut render_pass =
                         render_context.begin_tracked_render_pass(render_pass_descriptor.clone());
@@ -123,6 +177,118 @@ impl ViewNode for MainTransmissivePass3dNode {
     }
 }
 
+/// Computes the ranges of `items` to render in each step of the transmissive pass, along with
+/// whether the main texture should be copied to the transmission texture before each step.
+///
+/// Consecutive items sharing the same number of steps (see [`effective_transmission_steps`]) are
+/// grouped together, and each group is split into that many steps. Groups with `0` steps reuse the
+/// previous copy, unless no copy has been made yet.
+fn transmissive_steps(items: &[Transmissive3d], camera_steps: usize) -> Vec<(Range<usize>, bool)> {
+    let item_steps = effective_transmission_steps(items, camera_steps);
+    let mut steps = Vec::new();
+    let mut has_copied = false;
+    let mut group_start = 0;
+
+    while group_start < items.len() {
+        let group_steps = item_steps[group_start];
+        let group_end = item_steps[group_start..]
+            .iter()
+            .position(|&steps| steps != group_steps)
+            .map_or(items.len(), |len| group_start + len);
+
+        if group_steps == 0 && has_copied {
+            steps.push((group_start..group_end, false));
+        } else {
+            steps.extend(
+                split_range_by_overlap(items, group_start..group_end, group_steps.max(1))
+                    .into_iter()
+                    .map(|range| (range, true)),
+            );
+            has_copied = true;
+        }
+
+        group_start = group_end;
+    }
+
+    steps
+}
+
+/// Returns the number of steps to use for each of the back-to-front sorted `items`.
+///
+/// This is the item's own number of steps (overridden by its material via
+/// [`Transmissive3d::specular_transmission_steps`], or `camera_steps`), raised to the overridden
+/// number of steps of any item in front of it that overlaps it in screen space. This way, an item
+/// with a higher number of steps (e.g. a single “hero” glass object) also splits the items seen
+/// through it into that many steps, instead of only being grouped with itself.
+fn effective_transmission_steps(items: &[Transmissive3d], camera_steps: usize) -> Vec<usize> {
+    let mut item_steps = vec![0; items.len()];
+    let mut overrides: Vec<(Option<Rect>, usize)> = Vec::new();
+
+    for (index, item) in items.iter().enumerate().rev() {
+        item_steps[index] = overrides
+            .iter()
+            .filter(|(bounds, _)| may_overlap(*bounds, item.screen_space_bounds))
+            .map(|(_, steps)| *steps)
+            .fold(
+                item.specular_transmission_steps.unwrap_or(camera_steps),
+                usize::max,
+            );
+
+        if let Some(steps) = item.specular_transmission_steps {
+            overrides.push((item.screen_space_bounds, steps));
+        }
+    }
+
+    item_steps
+}
+
+/// Returns whether two screen space bounds may overlap. Unknown bounds are assumed to cover the
+/// whole screen.
+fn may_overlap(a: Option<Rect>, b: Option<Rect>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => !a.intersect(b).is_empty(),
+        _ => true,
+    }
+}
+
+/// Splits a [`Range`] of back-to-front sorted `items` into at most `max_num_splits` sub-ranges,
+/// starting a new sub-range only when an item overlaps (in screen space) an item of the current one.
+///
+/// Falls back to [`split_range`] if the [`Transmissive3d::screen_space_bounds`] of any item is unknown.
+fn split_range_by_overlap(
+    items: &[Transmissive3d],
+    range: Range<usize>,
+    max_num_splits: usize,
+) -> Vec<Range<usize>> {
+    if items[range.clone()]
+        .iter()
+        .any(|item| item.screen_space_bounds.is_none())
+    {
+        return split_range(range, max_num_splits).collect();
+    }
+
+    let mut splits = Vec::new();
+    let mut split_start = range.start;
+    let mut split_bounds: Vec<Rect> = Vec::new();
+
+    for (index, item) in items.iter().enumerate().take(range.end).skip(range.start) {
+        let bounds = item.screen_space_bounds.unwrap();
+        if splits.len() + 1 < max_num_splits
+            && split_bounds
+                .iter()
+                .any(|split_bound| !split_bound.intersect(bounds).is_empty())
+        {
+            splits.push(split_start..index);
+            split_start = index;
+            split_bounds.clear();
+        }
+        split_bounds.push(bounds);
+    }
+
+    splits.push(split_start..range.end);
+    splits
+}
+
 /// Splits a [`Range`] into at most `max_num_splits` sub-ranges without overlaps
 ///
 /// Properly takes into account remainders of inexact divisions (by adding extra
@@ -149,3 +315,112 @@ fn split_range(range: Range<usize>, max_num_splits: usize) -> impl Iterator<Item
         result
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_render::{
+        render_phase::{Draw, DrawError, DrawFunctions, PhaseItemExtraIndex, TrackedRenderPass},
+        render_resource::CachedRenderPipelineId,
+    };
+
+    struct NoopDraw;
+
+    impl Draw<Transmissive3d> for NoopDraw {
+        fn draw<'w>(
+            &mut self,
+            _world: &'w World,
+            _pass: &mut TrackedRenderPass<'w>,
+            _view: Entity,
+            _item: &Transmissive3d,
+        ) -> Result<(), DrawError> {
+            Ok(())
+        }
+    }
+
+    /// Creates back-to-front sorted items from their step overrides and screen space bounds.
+    fn items(items: &[(Option<usize>, Option<Rect>)]) -> Vec<Transmissive3d> {
+        let draw_function = DrawFunctions::<Transmissive3d>::default()
+            .write()
+            .add(NoopDraw);
+        items
+            .iter()
+            .enumerate()
+            .map(
+                |(index, &(specular_transmission_steps, screen_space_bounds))| Transmissive3d {
+                    distance: index as f32,
+                    pipeline: CachedRenderPipelineId::INVALID,
+                    entity: Entity::PLACEHOLDER,
+                    draw_function,
+                    batch_range: 0..1,
+                    extra_index: PhaseItemExtraIndex::NONE,
+                    specular_transmission_steps,
+                    screen_space_bounds,
+                },
+            )
+            .collect()
+    }
+
+    #[test]
+    fn overlapping_items_use_separate_steps() {
+        let bounds = Some(Rect::new(-0.5, -0.5, 0.5, 0.5));
+        let items = items(&[(None, bounds), (None, bounds), (None, bounds)]);
+        assert_eq!(
+            transmissive_steps(&items, 3),
+            vec![(0..1, true), (1..2, true), (2..3, true)]
+        );
+        assert_eq!(
+            transmissive_steps(&items, 2),
+            vec![(0..1, true), (1..3, true)]
+        );
+    }
+
+    #[test]
+    fn disjoint_items_share_a_step() {
+        let items = items(&[
+            (None, Some(Rect::new(-1.0, -1.0, -0.5, -0.5))),
+            (None, Some(Rect::new(-0.25, -0.25, 0.25, 0.25))),
+            (None, Some(Rect::new(0.5, 0.5, 1.0, 1.0))),
+        ]);
+        assert_eq!(transmissive_steps(&items, 3), vec![(0..3, true)]);
+    }
+
+    #[test]
+    fn unknown_bounds_split_evenly() {
+        let bounds = Some(Rect::new(-1.0, -1.0, -0.5, -0.5));
+        let items = items(&[(None, bounds), (None, None), (None, bounds), (None, bounds)]);
+        assert_eq!(
+            transmissive_steps(&items, 2),
+            vec![(0..2, true), (2..4, true)]
+        );
+    }
+
+    #[test]
+    fn overridden_steps_apply_to_items_seen_through() {
+        let hero_bounds = Some(Rect::new(-0.5, -0.5, 0.5, 0.5));
+        let items = items(&[
+            (None, Some(Rect::new(0.75, 0.75, 1.0, 1.0))),
+            (None, hero_bounds),
+            (None, hero_bounds),
+            (Some(3), hero_bounds),
+        ]);
+        assert_eq!(effective_transmission_steps(&items, 1), vec![1, 3, 3, 3]);
+        assert_eq!(
+            transmissive_steps(&items, 1),
+            vec![(0..1, true), (1..2, true), (2..3, true), (3..4, true)]
+        );
+    }
+
+    #[test]
+    fn zero_steps_reuse_previous_copy() {
+        let items = items(&[
+            (Some(0), Some(Rect::new(-1.0, -1.0, -0.5, -0.5))),
+            (None, Some(Rect::new(-0.25, -0.25, 0.25, 0.25))),
+            (Some(0), Some(Rect::new(0.5, 0.5, 1.0, 1.0))),
+        ]);
+        assert_eq!(
+            transmissive_steps(&items, 1),
+            vec![(0..1, true), (1..2, true), (2..3, false)]
+        );
+    }
+}
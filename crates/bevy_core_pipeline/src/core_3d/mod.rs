@@ -2,6 +2,8 @@ mod camera_3d;
 mod main_opaque_pass_3d_node;
 mod main_transmissive_pass_3d_node;
 mod main_transparent_pass_3d_node;
+mod transmission_blur;
+mod transmissive_reflection_capture;
 
 pub mod graph {
     use bevy_render::render_graph::{RenderLabel, RenderSubGraph};
@@ -24,6 +26,7 @@ pub mod graph {
         MainOpaquePass,
         MainTransmissivePass,
         MainTransparentPass,
+        TransmissiveReflectionCapture,
         EndMainPass,
         Taa,
         MotionBlur,
@@ -63,6 +66,26 @@ pub const DEPTH_TEXTURE_SAMPLING_SUPPORTED: bool = false;
 #[cfg(any(feature = "webgpu", not(target_arch = "wasm32")))]
 pub const DEPTH_TEXTURE_SAMPLING_SUPPORTED: bool = true;
 
+/// True if [`ScreenSpaceTransmissionBlur::MipChain`] and [`Camera3d::transmission_texture_scale`]
+/// values below `1.0` are supported on this platform.
+///
+/// WebGL 2 does not support rendering to a specific mip level of a texture while
+/// sampling from another one, so the mip chain can't be generated there.
+#[cfg(not(any(feature = "webgpu", not(target_arch = "wasm32"))))]
+pub const TRANSMISSION_MIP_CHAIN_SUPPORTED: bool = false;
+
+/// True if [`ScreenSpaceTransmissionBlur::MipChain`] and [`Camera3d::transmission_texture_scale`]
+/// values below `1.0` are supported on this platform.
+///
+/// WebGL 2 does not support rendering to a specific mip level of a texture while
+/// sampling from another one, so the mip chain can't be generated there.
+#[cfg(any(feature = "webgpu", not(target_arch = "wasm32")))]
+pub const TRANSMISSION_MIP_CHAIN_SUPPORTED: bool = true;
+
+/// The maximum number of mip levels of the [`ViewTransmissionTexture`], when using
+/// [`ScreenSpaceTransmissionBlur::MipChain`].
+const MAX_TRANSMISSION_MIP_LEVELS: u32 = 8;
+
 use std::ops::Range;
 
 use bevy_asset::{AssetId, UntypedAssetId};
@@ -70,10 +93,12 @@ use bevy_color::LinearRgba;
 pub use camera_3d::*;
 pub use main_opaque_pass_3d_node::*;
 pub use main_transparent_pass_3d_node::*;
+pub use transmission_blur::*;
+pub use transmissive_reflection_capture::*;
 
 use bevy_app::{App, Plugin, PostUpdate};
 use bevy_ecs::{entity::EntityHashSet, prelude::*};
-use bevy_math::FloatOrd;
+use bevy_math::{FloatOrd, Rect, UVec2};
 use bevy_render::{
     camera::{Camera, ExtractedCamera},
     extract_component::ExtractComponentPlugin,
@@ -85,8 +110,9 @@ use bevy_render::{
         ViewSortedRenderPhases,
     },
     render_resource::{
-        BindGroupId, CachedRenderPipelineId, Extent3d, FilterMode, Sampler, SamplerDescriptor,
-        Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages, TextureView,
+        BindGroupId, CachedRenderPipelineId, Extent3d, FilterMode, PipelineCache, Sampler,
+        SamplerDescriptor, Texture, TextureDescriptor, TextureDimension, TextureFormat,
+        TextureUsages, TextureView,
     },
     renderer::RenderDevice,
     texture::{BevyDefault, ColorAttachment, Image, TextureCache},
@@ -121,7 +147,13 @@ impl Plugin for Core3dPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Camera3d>()
             .register_type::<ScreenSpaceTransmissionQuality>()
-            .add_plugins((SkyboxPlugin, ExtractComponentPlugin::<Camera3d>::default()))
+            .register_type::<ScreenSpaceTransmissionBlur>()
+            .add_plugins((
+                SkyboxPlugin,
+                TransmissionBlurPlugin,
+                TransmissiveReflectionCapturePlugin,
+                ExtractComponentPlugin::<Camera3d>::default(),
+            ))
             .add_systems(PostUpdate, check_msaa);
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
@@ -182,6 +214,10 @@ impl Plugin for Core3dPlugin {
                 Core3d,
                 Node3d::MainTransparentPass,
             )
+            .add_render_graph_node::<ViewNodeRunner<TransmissiveReflectionCaptureNode>>(
+                Core3d,
+                Node3d::TransmissiveReflectionCapture,
+            )
             .add_render_graph_node::<EmptyNode>(Core3d, Node3d::EndMainPass)
             .add_render_graph_node::<ViewNodeRunner<DepthOfFieldNode>>(Core3d, Node3d::DepthOfField)
             .add_render_graph_node::<ViewNodeRunner<TonemappingNode>>(Core3d, Node3d::Tonemapping)
@@ -198,6 +234,7 @@ impl Plugin for Core3dPlugin {
                     Node3d::MainOpaquePass,
                     Node3d::MainTransmissivePass,
                     Node3d::MainTransparentPass,
+                    Node3d::TransmissiveReflectionCapture,
                     Node3d::EndMainPass,
                     Node3d::Tonemapping,
                     Node3d::EndMainPassPostProcessing,
@@ -373,6 +410,19 @@ pub struct Transmissive3d {
     pub draw_function: DrawFunctionId,
     pub batch_range: Range<u32>,
     pub extra_index: PhaseItemExtraIndex,
+    /// Overrides [`Camera3d::screen_space_specular_transmission_steps`] for this item.
+    ///
+    /// Consecutive items with the same number of steps are rendered together, split into that many steps.
+    /// Items behind this one that overlap it in screen space use at least this many steps, so that they
+    /// remain visible through it. Has no effect if the camera's number of steps is `0`, since no
+    /// transmission texture is available then.
+    pub specular_transmission_steps: Option<usize>,
+    /// A conservative estimate of the screen space area this item draws to and reads from (through the
+    /// [`ViewTransmissionTexture`], including refraction and blur), in normalized device coordinates.
+    ///
+    /// Items that don't overlap can share a step of the [`Transmissive3d`] pass, saving texture copies.
+    /// If [`None`], the item is assumed to potentially cover the whole screen.
+    pub screen_space_bounds: Option<Rect>,
 }
 
 impl PhaseItem for Transmissive3d {
@@ -687,6 +737,9 @@ pub struct ViewTransmissionTexture {
     pub texture: Texture,
     pub view: TextureView,
     pub sampler: Sampler,
+    /// The number of mip levels of [`ViewTransmissionTexture::texture`]. Greater than `1` only
+    /// when using [`ScreenSpaceTransmissionBlur::MipChain`].
+    pub mip_level_count: u32,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -698,10 +751,18 @@ pub fn prepare_core_3d_transmission_textures(
     alpha_mask_3d_phases: Res<ViewBinnedRenderPhases<AlphaMask3d>>,
     transmissive_3d_phases: Res<ViewSortedRenderPhases<Transmissive3d>>,
     transparent_3d_phases: Res<ViewSortedRenderPhases<Transparent3d>>,
-    views_3d: Query<(Entity, &ExtractedCamera, &Camera3d, &ExtractedView)>,
+    pipeline_cache: Res<PipelineCache>,
+    views_3d: Query<(
+        Entity,
+        &ExtractedCamera,
+        &Camera3d,
+        &ExtractedView,
+        Option<&TransmissionBlurPipelineId>,
+        Option<&TransmissionDownsamplePipelineId>,
+    )>,
 ) {
     let mut textures = HashMap::default();
-    for (entity, camera, camera_3d, view) in &views_3d {
+    for (entity, camera, camera_3d, view, blur_pipeline_id, downsample_pipeline_id) in &views_3d {
         if !opaque_3d_phases.contains_key(&entity)
             || !alpha_mask_3d_phases.contains_key(&entity)
             || !transparent_3d_phases.contains_key(&entity)
@@ -727,16 +788,56 @@ pub fn prepare_core_3d_transmission_textures(
             continue;
         }
 
+        // Mip chains and scaled down textures are rendered into by pipelines that may still be
+        // compiling, in which case a full size texture without a mip chain is used meanwhile, so
+        // that the transmission texture never goes unwritten
+        let is_ready = |pipeline_id: Option<CachedRenderPipelineId>| {
+            pipeline_id.is_some_and(|id| pipeline_cache.get_render_pipeline(id).is_some())
+        };
+
+        let mip_chain = TRANSMISSION_MIP_CHAIN_SUPPORTED
+            && camera_3d.screen_space_specular_transmission_blur
+                == ScreenSpaceTransmissionBlur::MipChain
+            && is_ready(blur_pipeline_id.map(|id| id.0));
+
+        let transmission_texture_size = if TRANSMISSION_MIP_CHAIN_SUPPORTED
+            && is_ready(downsample_pipeline_id.map(|id| id.0))
+        {
+            (physical_target_size.as_vec2() * camera_3d.transmission_texture_scale.clamp(0.0, 1.0))
+                .as_uvec2()
+                .max(UVec2::ONE)
+        } else {
+            physical_target_size
+        };
+
+        // Each mip level is half the size of the previous one, down to 1 pixel
+        let mip_level_count = if mip_chain {
+            (transmission_texture_size.min_element().ilog2() + 1).min(MAX_TRANSMISSION_MIP_LEVELS)
+        } else {
+            1
+        };
+
+        // Scaled down transmission textures are downsampled from the main texture instead of copied
+        let scaled = transmission_texture_size != physical_target_size;
+
         let cached_texture = textures
-            .entry(camera.target.clone())
+            .entry((
+                camera.target.clone(),
+                transmission_texture_size,
+                mip_level_count,
+            ))
             .or_insert_with(|| {
-                let usage = TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST;
+                let mut usage = TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST;
+                if mip_level_count > 1 || scaled {
+                    // The mip chain (and the scaled down copy) is generated by rendering into the texture
+                    usage |= TextureUsages::RENDER_ATTACHMENT;
+                }
 
                 // The size of the transmission texture
                 let size = Extent3d {
                     depth_or_array_layers: 1,
-                    width: physical_target_size.x,
-                    height: physical_target_size.y,
+                    width: transmission_texture_size.x,
+                    height: transmission_texture_size.y,
                 };
 
                 let format = if view.hdr {
@@ -748,7 +849,7 @@ pub fn prepare_core_3d_transmission_textures(
                 let descriptor = TextureDescriptor {
                     label: Some("view_transmission_texture"),
                     size,
-                    mip_level_count: 1,
+                    mip_level_count,
                     sample_count: 1, // No need for MSAA, as we'll only copy the main texture here
                     dimension: TextureDimension::D2,
                     format,
@@ -760,10 +861,19 @@ pub fn prepare_core_3d_transmission_textures(
             })
             .clone();
 
+        // The mip chain is sampled between mip levels, and scaled down textures are bilinearly upsampled,
+        // so both need linear filtering
+        let filter_mode = if mip_level_count > 1 || scaled {
+            FilterMode::Linear
+        } else {
+            FilterMode::Nearest
+        };
+
         let sampler = render_device.create_sampler(&SamplerDescriptor {
             label: Some("view_transmission_sampler"),
-            mag_filter: FilterMode::Nearest,
-            min_filter: FilterMode::Nearest,
+            mag_filter: filter_mode,
+            min_filter: filter_mode,
+            mipmap_filter: filter_mode,
             ..Default::default()
         });
 
@@ -771,6 +881,7 @@ pub fn prepare_core_3d_transmission_textures(
             texture: cached_texture.texture,
             view: cached_texture.default_view,
             sampler,
+            mip_level_count,
         });
     }
 }
@@ -0,0 +1,330 @@
+//! Generates a blurred mip chain of the [`ViewTransmissionTexture`], used by
+//! [`ScreenSpaceTransmissionBlur::MipChain`], and downsamples the main texture into
+//! it when [`Camera3d::transmission_texture_scale`] is below `1.0`.
+
+use super::{
+    prepare_core_3d_transmission_textures, Camera3d, ScreenSpaceTransmissionBlur,
+    ViewTransmissionTexture,
+};
+use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    render_resource::{
+        binding_types::{sampler, texture_2d},
+        *,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::BevyDefault,
+    view::{ExtractedView, ViewTarget},
+    Render, RenderApp, RenderSet,
+};
+
+const TRANSMISSION_BLUR_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(6284917358104726391);
+
+pub struct TransmissionBlurPlugin;
+
+impl Plugin for TransmissionBlurPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TRANSMISSION_BLUR_SHADER_HANDLE,
+            "transmission_blur.wgsl",
+            Shader::from_wgsl
+        );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<TransmissionBlurPipeline>>()
+            .add_systems(
+                Render,
+                (
+                    // The transmission texture is only scaled down (or given a mip chain) once
+                    // these pipelines are ready
+                    prepare_transmission_blur_pipelines
+                        .in_set(RenderSet::Prepare)
+                        .before(prepare_core_3d_transmission_textures),
+                    prepare_transmission_blur_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<TransmissionBlurPipeline>();
+    }
+}
+
+#[derive(Resource)]
+pub struct TransmissionBlurPipeline {
+    /// Layout with a texture and a sampler
+    pub bind_group_layout: BindGroupLayout,
+    pub sampler: Sampler,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct TransmissionBlurPipelineKey {
+    pub texture_format: TextureFormat,
+    /// Whether to downsample by an arbitrary factor with a box filter (e.g. from the main texture
+    /// into a scaled down texture), instead of by a factor of two (from one mip level into the next).
+    pub box_filter: bool,
+}
+
+/// The pipeline downsampling each mip level of the [`ViewTransmissionTexture`] into the next one.
+#[derive(Component)]
+pub struct TransmissionBlurPipelineId(pub CachedRenderPipelineId);
+
+/// The pipeline downsampling the main texture into a scaled down [`ViewTransmissionTexture`].
+#[derive(Component)]
+pub struct TransmissionDownsamplePipelineId(pub CachedRenderPipelineId);
+
+/// The bind groups and render targets used to downsample each mip level of
+/// the [`ViewTransmissionTexture`] into the next one.
+#[derive(Component)]
+pub struct ViewTransmissionBlurBindGroups {
+    /// View of mip level `0`, the target when downsampling from the main texture
+    pub base_view: TextureView,
+    /// Reads from mip level `i`, for `i` in `0..mip_level_count - 1`
+    pub bind_groups: Box<[BindGroup]>,
+    /// Views of mip level `i + 1`, for `i` in `0..mip_level_count - 1`
+    pub target_views: Box<[TextureView]>,
+}
+
+impl FromWorld for TransmissionBlurPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let bind_group_layout = render_device.create_bind_group_layout(
+            "transmission_blur_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // Input texture binding
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // Sampler binding
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("transmission_blur_sampler"),
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+
+        TransmissionBlurPipeline {
+            bind_group_layout,
+            sampler,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for TransmissionBlurPipeline {
+    type Key = TransmissionBlurPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        RenderPipelineDescriptor {
+            label: Some("transmission_blur_pipeline".into()),
+            layout: vec![self.bind_group_layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: TRANSMISSION_BLUR_SHADER_HANDLE,
+                shader_defs: vec![],
+                entry_point: if key.box_filter {
+                    "downsample_box".into()
+                } else {
+                    "downsample".into()
+                },
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+pub fn prepare_transmission_blur_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TransmissionBlurPipeline>>,
+    pipeline: Res<TransmissionBlurPipeline>,
+    views: Query<(Entity, &Camera3d, &ExtractedView)>,
+) {
+    for (entity, camera_3d, view) in &views {
+        let scaled = camera_3d.transmission_texture_scale < 1.0;
+        if camera_3d.screen_space_specular_transmission_blur
+            != ScreenSpaceTransmissionBlur::MipChain
+            && !scaled
+        {
+            continue;
+        }
+
+        // Must match the format of the `ViewTransmissionTexture`
+        let texture_format = if view.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &pipeline,
+            TransmissionBlurPipelineKey {
+                texture_format,
+                box_filter: false,
+            },
+        );
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(TransmissionBlurPipelineId(pipeline_id));
+
+        if scaled {
+            let pipeline_id = pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                TransmissionBlurPipelineKey {
+                    texture_format,
+                    box_filter: true,
+                },
+            );
+
+            entity_commands.insert(TransmissionDownsamplePipelineId(pipeline_id));
+        }
+    }
+}
+
+pub fn prepare_transmission_blur_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    pipeline: Res<TransmissionBlurPipeline>,
+    views: Query<(Entity, &ViewTransmissionTexture), With<TransmissionBlurPipelineId>>,
+) {
+    for (entity, transmission) in &views {
+        let mip_view = |base_mip_level| {
+            transmission.texture.create_view(&TextureViewDescriptor {
+                label: Some("view_transmission_texture_mip"),
+                base_mip_level,
+                mip_level_count: Some(1),
+                ..Default::default()
+            })
+        };
+
+        let mut bind_groups = Vec::with_capacity(transmission.mip_level_count as usize - 1);
+        let mut target_views = Vec::with_capacity(transmission.mip_level_count as usize - 1);
+        for mip in 1..transmission.mip_level_count {
+            bind_groups.push(render_device.create_bind_group(
+                "transmission_blur_bind_group",
+                &pipeline.bind_group_layout,
+                &BindGroupEntries::sequential((&mip_view(mip - 1), &pipeline.sampler)),
+            ));
+            target_views.push(mip_view(mip));
+        }
+
+        commands
+            .entity(entity)
+            .insert(ViewTransmissionBlurBindGroups {
+                base_view: mip_view(0),
+                bind_groups: bind_groups.into_boxed_slice(),
+                target_views: target_views.into_boxed_slice(),
+            });
+    }
+}
+
+/// Downsamples the main texture into mip level `0` of a scaled down [`ViewTransmissionTexture`].
+///
+/// Does nothing if the pipeline isn't ready yet.
+pub(crate) fn downsample_to_transmission_texture(
+    render_context: &mut RenderContext,
+    pipeline_cache: &PipelineCache,
+    transmission_blur_pipeline: &TransmissionBlurPipeline,
+    pipeline_id: &TransmissionDownsamplePipelineId,
+    bind_groups: &ViewTransmissionBlurBindGroups,
+    main_texture_view: &TextureView,
+) {
+    let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+        return;
+    };
+
+    // The main texture may change between frames, so this bind group can't be prepared ahead of time
+    let bind_group = render_context.render_device().create_bind_group(
+        "transmission_downsample_bind_group",
+        &transmission_blur_pipeline.bind_group_layout,
+        &BindGroupEntries::sequential((main_texture_view, &transmission_blur_pipeline.sampler)),
+    );
+
+    downsample_pass(
+        render_context,
+        pipeline,
+        &bind_group,
+        &bind_groups.base_view,
+        "transmission_downsample_pass",
+    );
+}
+
+/// Downsamples mip level `0` of the [`ViewTransmissionTexture`] into the rest of its mip chain.
+///
+/// Does nothing if the pipeline isn't ready yet.
+pub(crate) fn blur_transmission_texture(
+    render_context: &mut RenderContext,
+    pipeline_cache: &PipelineCache,
+    pipeline_id: &TransmissionBlurPipelineId,
+    bind_groups: &ViewTransmissionBlurBindGroups,
+) {
+    let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+        return;
+    };
+
+    for (bind_group, target_view) in bind_groups
+        .bind_groups
+        .iter()
+        .zip(bind_groups.target_views.iter())
+    {
+        downsample_pass(
+            render_context,
+            pipeline,
+            bind_group,
+            target_view,
+            "transmission_blur_pass",
+        );
+    }
+}
+
+pub(crate) fn downsample_pass(
+    render_context: &mut RenderContext,
+    pipeline: &RenderPipeline,
+    bind_group: &BindGroup,
+    target_view: &TextureView,
+    label: &'static str,
+) {
+    let mut pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: target_view,
+            resolve_target: None,
+            ops: Operations::default(),
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    pass.set_render_pipeline(pipeline);
+    pass.set_bind_group(0, bind_group, &[]);
+    pass.draw(0..3, 0..1);
+}
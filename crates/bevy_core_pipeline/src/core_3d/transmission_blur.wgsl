@@ -0,0 +1,61 @@
+// Downsamples one mip level of the view transmission texture into the next one, building a blur
+// pyramid that can be sampled at a level matching the roughness of transmissive materials.
+//
+// Uses the downsampling filter from the dual filtering (“dual Kawase”) blur:
+// * Bandwidth-Efficient Rendering - Marius Bjørge - SIGGRAPH 2015
+//
+// Also downsamples the main texture into a scaled down texture by an arbitrary factor, with a
+// box filter.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var input_texture: texture_2d<f32>;
+@group(0) @binding(1) var input_sampler: sampler;
+
+@fragment
+fn downsample(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let texel_size = 1.0 / vec2<f32>(textureDimensions(input_texture));
+
+    // The center sample covers the 2x2 input texels under this output texel, and the four
+    // diagonal samples (each also bilinearly filtered) widen the kernel to avoid aliasing.
+    var sum = textureSample(input_texture, input_sampler, in.uv) * 4.0;
+    sum += textureSample(input_texture, input_sampler, in.uv + vec2(-texel_size.x, -texel_size.y));
+    sum += textureSample(input_texture, input_sampler, in.uv + vec2(texel_size.x, -texel_size.y));
+    sum += textureSample(input_texture, input_sampler, in.uv + vec2(-texel_size.x, texel_size.y));
+    sum += textureSample(input_texture, input_sampler, in.uv + vec2(texel_size.x, texel_size.y));
+
+    return sum / 8.0;
+}
+
+// The maximum number of bilinear taps per axis taken by `downsample_box`, enough to cover every
+// input texel when downsampling by a factor of up to 16.
+const MAX_BOX_TAPS: u32 = 8u;
+
+@fragment
+fn downsample_box(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let input_size = vec2<f32>(textureDimensions(input_texture));
+
+    // The size of the output texel, in UV units
+    let output_texel_size = fwidth(in.uv);
+
+    // Each bilinear tap averages 2x2 input texels, so space the taps 2 input texels apart to
+    // cover the whole footprint of the output texel (e.g. 1 tap at a scale of 0.5, 2x2 taps at
+    // a scale of 0.25), instead of skipping input texels and aliasing.
+    let taps = clamp(
+        vec2<u32>(ceil(output_texel_size * input_size * 0.5)),
+        vec2(1u),
+        vec2(MAX_BOX_TAPS)
+    );
+    let tap_spacing = output_texel_size / vec2<f32>(taps);
+    let first_tap = in.uv - 0.5 * output_texel_size + 0.5 * tap_spacing;
+
+    var sum = vec4(0.0);
+    for (var y = 0u; y < taps.y; y += 1u) {
+        for (var x = 0u; x < taps.x; x += 1u) {
+            let uv = first_tap + vec2(f32(x), f32(y)) * tap_spacing;
+            sum += textureSampleLevel(input_texture, input_sampler, uv, 0.0);
+        }
+    }
+
+    return sum / f32(taps.x * taps.y);
+}
@@ -0,0 +1,271 @@
+//! Captures a low resolution copy of the main texture once [`Transmissive3d`] and
+//! [`Transparent3d`] items have been drawn, so that reflections traced in the next frame
+//! (before those items are drawn) can include an approximate version of them.
+//!
+//! [`Transmissive3d`]: super::Transmissive3d
+//! [`Transparent3d`]: super::Transparent3d
+
+use super::{
+    downsample_pass, TransmissionBlurPipeline, TransmissionBlurPipelineKey,
+    TRANSMISSION_MIP_CHAIN_SUPPORTED,
+};
+use bevy_app::{App, Plugin};
+use bevy_ecs::{entity::EntityHashMap, prelude::*, query::QueryItem};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, ExtractedCamera},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::*,
+    renderer::{RenderContext, RenderDevice},
+    texture::{BevyDefault, CachedTexture},
+    view::{ExtractedView, ViewTarget},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::warn_once;
+
+pub struct TransmissiveReflectionCapturePlugin;
+
+impl Plugin for TransmissiveReflectionCapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TransmissiveReflectionCapture>()
+            .add_plugins(ExtractComponentPlugin::<TransmissiveReflectionCapture>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<TransmissiveReflectionCaptureTextures>()
+            .add_systems(
+                Render,
+                (
+                    prepare_transmissive_reflection_capture_pipelines.in_set(RenderSet::Prepare),
+                    prepare_transmissive_reflection_capture_textures
+                        .in_set(RenderSet::PrepareResources),
+                ),
+            );
+    }
+}
+
+/// Add this component to a 3D camera to capture a low resolution copy of the rendered scene,
+/// including transmissive and transparent items, for use by reflections in the following frame.
+///
+/// Transmissive and transparent items are drawn after reflections are traced, and don't write
+/// to the depth prepass, so they otherwise vanish from reflective surfaces such as mirrors and
+/// water. Reflections use the capture where it noticeably differs from the main texture (i.e.
+/// where transmissive or transparent items were drawn), showing an approximate, one frame old
+/// version of them, and the main texture elsewhere.
+///
+/// Currently only used by screen space reflections. Environment map reflections (including
+/// those screen space reflections fall back to when their rays miss) come from prefiltered
+/// cubemaps, which this doesn't affect.
+///
+/// **Note:** Not supported on WebGL 2, as the capture can't be rendered there.
+#[derive(Component, Reflect, Clone, Copy, Debug, ExtractComponent)]
+#[extract_component_filter(With<Camera>)]
+#[reflect(Component, Default, Debug)]
+pub struct TransmissiveReflectionCapture {
+    /// The resolution of the capture, relative to the main texture. Clamped to the `(0.0, 1.0]` range.
+    ///
+    /// Reflections are usually blurry and distorted enough that a low resolution capture is
+    /// indistinguishable from a full resolution one, while being much cheaper to render and sample.
+    pub resolution_scale: f32,
+}
+
+impl Default for TransmissiveReflectionCapture {
+    fn default() -> Self {
+        Self {
+            resolution_scale: 0.5,
+        }
+    }
+}
+
+/// The texture the [`TransmissiveReflectionCapture`] of a view is rendered to.
+///
+/// It holds the previous frame's capture until the [`TransmissiveReflectionCaptureNode`] runs.
+#[derive(Component)]
+pub struct ViewTransmissiveReflectionCaptureTexture {
+    pub texture: CachedTexture,
+    /// Whether the texture holds a capture from the previous frame. It doesn't when it was just
+    /// (re)created, e.g. on the first frame or after the view was resized.
+    pub has_previous_capture: bool,
+}
+
+/// The textures the [`TransmissiveReflectionCapture`] of each view is rendered to, kept across
+/// frames so that each view reads back its own capture from the previous frame.
+///
+/// Entries are removed once their view no longer has a [`TransmissiveReflectionCapture`], or
+/// isn't rendered anymore (e.g. because its camera was deactivated).
+#[derive(Resource, Default)]
+pub struct TransmissiveReflectionCaptureTextures(
+    EntityHashMap<(TextureDescriptor<'static>, CachedTexture)>,
+);
+
+#[derive(Component)]
+pub struct TransmissiveReflectionCapturePipelineId(pub CachedRenderPipelineId);
+
+pub fn prepare_transmissive_reflection_capture_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<TransmissionBlurPipeline>>,
+    pipeline: Res<TransmissionBlurPipeline>,
+    views: Query<(Entity, &ExtractedView), With<TransmissiveReflectionCapture>>,
+) {
+    for (entity, view) in &views {
+        // Must match the format of the `ViewTransmissiveReflectionCaptureTexture`
+        let texture_format = if view.hdr {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &pipeline,
+            TransmissionBlurPipelineKey {
+                texture_format,
+                box_filter: true,
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(TransmissiveReflectionCapturePipelineId(pipeline_id));
+    }
+}
+
+pub fn prepare_transmissive_reflection_capture_textures(
+    mut commands: Commands,
+    mut capture_textures: ResMut<TransmissiveReflectionCaptureTextures>,
+    render_device: Res<RenderDevice>,
+    views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ExtractedView,
+        &TransmissiveReflectionCapture,
+    )>,
+) {
+    capture_textures
+        .0
+        .retain(|entity, _| views.contains(*entity));
+
+    if !TRANSMISSION_MIP_CHAIN_SUPPORTED {
+        if !views.is_empty() {
+            warn_once!("`TransmissiveReflectionCapture` is not supported on this platform.");
+        }
+        return;
+    }
+
+    for (entity, camera, view, capture) in &views {
+        let Some(physical_target_size) = camera.physical_target_size else {
+            continue;
+        };
+
+        let resolution_scale = if capture.resolution_scale > 0.0 {
+            capture.resolution_scale.min(1.0)
+        } else {
+            1.0
+        };
+
+        let descriptor = TextureDescriptor {
+            label: Some("view_transmissive_reflection_capture_texture"),
+            size: Extent3d {
+                width: ((physical_target_size.x as f32 * resolution_scale) as u32).max(1),
+                height: ((physical_target_size.y as f32 * resolution_scale) as u32).max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: if view.hdr {
+                ViewTarget::TEXTURE_FORMAT_HDR
+            } else {
+                TextureFormat::bevy_default()
+            },
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+
+        let mut has_previous_capture = true;
+        let (cached_descriptor, texture) = capture_textures.0.entry(entity).or_insert_with(|| {
+            has_previous_capture = false;
+            (
+                descriptor.clone(),
+                create_capture_texture(&render_device, &descriptor),
+            )
+        });
+        if *cached_descriptor != descriptor {
+            has_previous_capture = false;
+            *texture = create_capture_texture(&render_device, &descriptor);
+            *cached_descriptor = descriptor;
+        }
+
+        commands
+            .entity(entity)
+            .insert(ViewTransmissiveReflectionCaptureTexture {
+                texture: texture.clone(),
+                has_previous_capture,
+            });
+    }
+}
+
+fn create_capture_texture(
+    render_device: &RenderDevice,
+    descriptor: &TextureDescriptor,
+) -> CachedTexture {
+    let texture = render_device.create_texture(descriptor);
+    let default_view = texture.create_view(&TextureViewDescriptor::default());
+    CachedTexture {
+        texture,
+        default_view,
+    }
+}
+
+/// A [`bevy_render::render_graph::Node`] that downsamples the main texture into the
+/// [`ViewTransmissiveReflectionCaptureTexture`], after the main passes.
+#[derive(Default)]
+pub struct TransmissiveReflectionCaptureNode;
+
+impl ViewNode for TransmissiveReflectionCaptureNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static ViewTransmissiveReflectionCaptureTexture,
+        &'static TransmissiveReflectionCapturePipelineId,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (target, capture, pipeline_id): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
+            return Ok(());
+        };
+
+        let transmission_blur_pipeline = world.resource::<TransmissionBlurPipeline>();
+
+        // The main texture may change between frames, so this bind group can't be prepared ahead of time
+        let bind_group = render_context.render_device().create_bind_group(
+            "transmissive_reflection_capture_bind_group",
+            &transmission_blur_pipeline.bind_group_layout,
+            &BindGroupEntries::sequential((
+                target.main_texture_view(),
+                &transmission_blur_pipeline.sampler,
+            )),
+        );
+
+        downsample_pass(
+            render_context,
+            pipeline,
+            &bind_group,
+            &capture.texture.default_view,
+            "transmissive_reflection_capture_pass",
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,324 @@
+#define_import_path bevy_core_pipeline::fog
+
+// Distance fog, shared by the 2d and 3d pipelines. The 3d pipelines additionally tint the fog
+// color with directional light scattering, see `bevy_pbr::fog`.
+
+// Fog formulas adapted from:
+// https://learn.microsoft.com/en-us/windows/win32/direct3d9/fog-formulas
+// https://catlikecoding.com/unity/tutorials/rendering/part-14/
+// https://iquilezles.org/articles/fog/ (Atmospheric Fog and Scattering)
+
+struct Fog {
+    base_color: vec4<f32>,
+    directional_light_color: vec4<f32>,
+    // `be` and `bi` are allocated differently depending on the fog mode
+    //
+    // For Linear Fog:
+    //     be.x = start, be.y = end
+    // For Exponential and ExponentialSquared Fog:
+    //     be.x = density
+    // For Atmospheric Fog:
+    //     be = per-channel extinction density
+    //     bi = per-channel inscattering density
+    // For ExponentialHeight Fog:
+    //     be.x = density, be.y = height falloff, be.z = base height
+    // For LinearHeight Fog:
+    //     be.x = start, be.y = end
+    //     bi.x = base height, bi.y = top height
+    // For AerialPerspective Fog:
+    //     be = per-channel extinction density
+    //     bi = direction towards the sun
+    be: vec3<f32>,
+    directional_light_exponent: f32,
+    bi: vec3<f32>,
+    mode: u32,
+    // Density noise modulation. A frequency of zero means noise is disabled
+    noise_offset: vec3<f32>,
+    noise_frequency: f32,
+    noise_intensity: f32,
+    start_distance: f32,
+    max_opacity: f32,
+    // Inscattered color for `AERIAL_PERSPECTIVE_LUT_SIZE` view-sun angles. Entry `u` (normalized
+    // to the [0.0, 1.0] range) holds the angle whose cosine is `1.0 - 2.0 * (1.0 - u)²`. Only
+    // read in the aerial perspective mode, see `aerial_perspective_lut_sample()`
+    aerial_perspective_lut: array<vec4<f32>, 32>,
+}
+
+// Important: These must be kept in sync with `fog/mod.rs`
+const FOG_MODE_OFF: u32                   = 0u;
+const FOG_MODE_LINEAR: u32                = 1u;
+const FOG_MODE_EXPONENTIAL: u32           = 2u;
+const FOG_MODE_EXPONENTIAL_SQUARED: u32   = 3u;
+const FOG_MODE_ATMOSPHERIC: u32           = 4u;
+const FOG_MODE_EXPONENTIAL_HEIGHT: u32    = 5u;
+const FOG_MODE_LINEAR_HEIGHT: u32         = 6u;
+const FOG_MODE_AERIAL_PERSPECTIVE: u32    = 7u;
+
+const AERIAL_PERSPECTIVE_LUT_SIZE: u32    = 32u;
+
+// Lattice cells after which the noise repeats, so the noise offset can be wrapped on the CPU.
+// Must be kept in sync with `fog/mod.rs`
+const FOG_NOISE_PERIOD: f32               = 256.0;
+
+fn fog_noise_hash(p: vec3<f32>) -> f32 {
+    let wrapped = p - FOG_NOISE_PERIOD * floor(p / FOG_NOISE_PERIOD);
+    var q = fract(wrapped * 0.3183099 + vec3<f32>(0.1, 0.2, 0.3));
+    q *= 17.0;
+    return fract(q.x * q.y * q.z * (q.x + q.y + q.z));
+}
+
+// Trilinearly interpolated value noise, in the range [0.0, 1.0]
+fn fog_value_noise(p: vec3<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    return mix(
+        mix(
+            mix(fog_noise_hash(i), fog_noise_hash(i + vec3(1.0, 0.0, 0.0)), u.x),
+            mix(fog_noise_hash(i + vec3(0.0, 1.0, 0.0)), fog_noise_hash(i + vec3(1.0, 1.0, 0.0)), u.x),
+            u.y
+        ),
+        mix(
+            mix(fog_noise_hash(i + vec3(0.0, 0.0, 1.0)), fog_noise_hash(i + vec3(1.0, 0.0, 1.0)), u.x),
+            mix(fog_noise_hash(i + vec3(0.0, 1.0, 1.0)), fog_noise_hash(i + vec3(1.0, 1.0, 1.0)), u.x),
+            u.y
+        ),
+        u.z
+    );
+}
+
+// Returns the factor by which the fog density is modulated along the ray from `ray_start` to
+// `ray_end`, averaging a few noise samples along it. Returns `1.0` when noise is disabled.
+fn fog_noise_factor(
+    noise_offset: vec3<f32>,
+    noise_frequency: f32,
+    noise_intensity: f32,
+    ray_start: vec3<f32>,
+    ray_end: vec3<f32>,
+) -> f32 {
+    if noise_frequency == 0.0 || noise_intensity == 0.0 {
+        return 1.0;
+    }
+
+    var noise = 0.0;
+    for (var i = 0u; i < 4u; i += 1u) {
+        let position = mix(ray_start, ray_end, (f32(i) + 0.5) / 4.0);
+        noise += fog_value_noise((position - noise_offset) * noise_frequency);
+    }
+    noise /= 4.0;
+
+    return max(1.0 + noise_intensity * (noise * 2.0 - 1.0), 0.0);
+}
+
+fn linear_fog_intensity(fog_params: Fog, distance: f32) -> f32 {
+    let start = fog_params.be.x;
+    let end = fog_params.be.y;
+    // Equal distances produce a hard cutoff instead of a division by zero
+    return 1.0 - clamp((end - distance) / max(end - start, 0.0001), 0.0, 1.0);
+}
+
+fn exponential_fog_intensity(fog_params: Fog, distance: f32) -> f32 {
+    let density = fog_params.be.x;
+    return 1.0 - 1.0 / exp(distance * density);
+}
+
+fn exponential_squared_fog_intensity(fog_params: Fog, distance: f32) -> f32 {
+    let distance_times_density = distance * fog_params.be.x;
+    return 1.0 - 1.0 / exp(distance_times_density * distance_times_density);
+}
+
+fn exponential_height_fog_intensity(
+    fog_params: Fog,
+    distance: f32,
+    view_height: f32,
+    fragment_height: f32,
+) -> f32 {
+    let density = fog_params.be.x;
+    let height_falloff = fog_params.be.y;
+    let base_height = fog_params.be.z;
+
+    // Density at the camera, integrated along the ray towards the fragment
+    // (see https://iquilezles.org/articles/fog/, "Non constant fog density")
+    let view_density = density * exp(-height_falloff * (view_height - base_height));
+    let falloff_times_delta = height_falloff * (fragment_height - view_height);
+    var ray_factor = 1.0;
+    if abs(falloff_times_delta) > 0.0001 {
+        ray_factor = (1.0 - exp(-falloff_times_delta)) / falloff_times_delta;
+    }
+    return 1.0 - 1.0 / exp(distance * view_density * ray_factor);
+}
+
+// Integral of the linear height factor (1.0 at or below `base`, 0.0 at or above `top`)
+// from `base` to `height`
+fn linear_height_factor_integral(height: f32, base: f32, top: f32) -> f32 {
+    let thickness = max(top - base, 0.0001);
+    let h = min(height, top) - base;
+    if h <= 0.0 {
+        return h;
+    }
+    return h - h * h / (2.0 * thickness);
+}
+
+fn linear_height_fog_intensity(
+    fog_params: Fog,
+    distance: f32,
+    view_height: f32,
+    fragment_height: f32,
+) -> f32 {
+    let base_height = fog_params.bi.x;
+    let top_height = fog_params.bi.y;
+
+    // Average the height factor along the ray from the camera to the fragment
+    let height_delta = fragment_height - view_height;
+    var height_factor: f32;
+    if abs(height_delta) > 0.0001 {
+        height_factor = (
+            linear_height_factor_integral(fragment_height, base_height, top_height)
+                - linear_height_factor_integral(view_height, base_height, top_height)
+        ) / height_delta;
+    } else {
+        height_factor = clamp((top_height - view_height) / max(top_height - base_height, 0.0001), 0.0, 1.0);
+    }
+    return linear_fog_intensity(fog_params, distance) * height_factor;
+}
+
+fn atmospheric_fog(
+    fog_params: Fog,
+    input_color: vec4<f32>,
+    distance: f32,
+    fog_color: vec4<f32>,
+) -> vec4<f32> {
+    let extinction_factor = min(1.0 - 1.0 / exp(distance * fog_params.be), vec3(fog_params.max_opacity));
+    let inscattering_factor = min(1.0 - 1.0 / exp(distance * fog_params.bi), vec3(fog_params.max_opacity));
+    return vec4<f32>(
+        input_color.rgb * (1.0 - extinction_factor * fog_color.a)
+            + fog_color.rgb * inscattering_factor * fog_color.a,
+        input_color.a
+    );
+}
+
+// The two entries of `Fog::aerial_perspective_lut` to interpolate for a view direction
+struct AerialPerspectiveLutSample {
+    index: u32,
+    // The interpolation factor from entry `index` to entry `index + 1u`
+    blend: f32,
+}
+
+// Returns the entries of the aerial perspective lookup table to interpolate for a fragment.
+//
+// The table itself must be indexed by the caller, directly from the view's fog uniform binding:
+// an array passed by value would have to be copied to a local variable to be dynamically indexed
+// on all backends.
+fn aerial_perspective_lut_sample(
+    fog_params: Fog,
+    view_world_position: vec3<f32>,
+    fragment_world_position: vec3<f32>,
+) -> AerialPerspectiveLutSample {
+    let view_to_fragment = fragment_world_position - view_world_position;
+    let view_direction = view_to_fragment / max(length(view_to_fragment), 0.0001);
+
+    // Entries are denser close to the sun, see `Fog::aerial_perspective_lut`
+    let cos_theta = dot(view_direction, fog_params.bi);
+    let lut_position = (1.0 - sqrt(clamp((1.0 - cos_theta) * 0.5, 0.0, 1.0)))
+        * f32(AERIAL_PERSPECTIVE_LUT_SIZE - 1u);
+    let index = min(u32(lut_position), AERIAL_PERSPECTIVE_LUT_SIZE - 2u);
+    return AerialPerspectiveLutSample(index, lut_position - f32(index));
+}
+
+// `inscattered_color` is interpolated from the aerial perspective lookup table, with the alpha of
+// the fog's base color, see `aerial_perspective_lut_sample()`
+fn aerial_perspective_fog(
+    fog_params: Fog,
+    input_color: vec4<f32>,
+    distance: f32,
+    inscattered_color: vec4<f32>,
+) -> vec4<f32> {
+    let scattered_factor = min(1.0 - exp(-distance * fog_params.be), vec3(fog_params.max_opacity))
+        * inscattered_color.a;
+    return vec4<f32>(
+        input_color.rgb * (1.0 - scattered_factor) + inscattered_color.rgb * scattered_factor,
+        input_color.a
+    );
+}
+
+// Returns the distance the fog falloff is evaluated for, given the `distance` of a fragment from
+// the view: the part of it past the fog's start distance, scaled by the density noise.
+fn fog_distance(
+    fog_params: Fog,
+    distance: f32,
+    view_world_position: vec3<f32>,
+    fragment_world_position: vec3<f32>,
+) -> f32 {
+    // For the density-based falloff modes, scaling the distance is equivalent to scaling the
+    // density. The linear modes have no density, so the noise moves their falloff instead
+    return max(distance - fog_params.start_distance, 0.0) * fog_noise_factor(
+        fog_params.noise_offset,
+        fog_params.noise_frequency,
+        fog_params.noise_intensity,
+        view_world_position,
+        fragment_world_position,
+    );
+}
+
+// Applies fog of the given `fog_color` to `input_color`, for a fragment whose `distance` was
+// already adjusted by `fog_distance()`. Positions are used by the height-based falloff modes.
+//
+// In the aerial perspective mode, `fog_color` must be the inscattered color, see
+// `aerial_perspective_fog()`. `bevy_pbr` otherwise tints it with directional light scattering.
+fn apply_fog_color(
+    fog_params: Fog,
+    fog_color: vec4<f32>,
+    input_color: vec4<f32>,
+    distance: f32,
+    view_world_position: vec3<f32>,
+    fragment_world_position: vec3<f32>,
+) -> vec4<f32> {
+    let view_height = view_world_position.y;
+    let fragment_height = fragment_world_position.y;
+
+    if fog_params.mode == FOG_MODE_ATMOSPHERIC {
+        return atmospheric_fog(fog_params, input_color, distance, fog_color);
+    } else if fog_params.mode == FOG_MODE_AERIAL_PERSPECTIVE {
+        return aerial_perspective_fog(fog_params, input_color, distance, fog_color);
+    }
+
+    var intensity: f32;
+    if fog_params.mode == FOG_MODE_LINEAR {
+        intensity = linear_fog_intensity(fog_params, distance);
+    } else if fog_params.mode == FOG_MODE_EXPONENTIAL {
+        intensity = exponential_fog_intensity(fog_params, distance);
+    } else if fog_params.mode == FOG_MODE_EXPONENTIAL_SQUARED {
+        intensity = exponential_squared_fog_intensity(fog_params, distance);
+    } else if fog_params.mode == FOG_MODE_EXPONENTIAL_HEIGHT {
+        intensity = exponential_height_fog_intensity(fog_params, distance, view_height, fragment_height);
+    } else if fog_params.mode == FOG_MODE_LINEAR_HEIGHT {
+        intensity = linear_height_fog_intensity(fog_params, distance, view_height, fragment_height);
+    } else {
+        return input_color;
+    }
+
+    let fog_alpha = fog_color.a * min(intensity, fog_params.max_opacity);
+    return vec4<f32>(mix(input_color.rgb, fog_color.rgb, fog_alpha), input_color.a);
+}
+
+// Applies fog of the given `fog_color` to `input_color`, for a fragment at `distance` from the
+// view. Positions are used by the height-based falloff modes, and for density noise.
+//
+// `fog_color` is the fog's base color, or the inscattered color in the aerial perspective mode.
+fn apply_fog(
+    fog_params: Fog,
+    fog_color: vec4<f32>,
+    input_color: vec4<f32>,
+    distance: f32,
+    view_world_position: vec3<f32>,
+    fragment_world_position: vec3<f32>,
+) -> vec4<f32> {
+    return apply_fog_color(
+        fog_params,
+        fog_color,
+        input_color,
+        fog_distance(fog_params, distance, view_world_position, fragment_world_position),
+        view_world_position,
+        fragment_world_position,
+    );
+}
@@ -0,0 +1,406 @@
+mod settings;
+
+#[allow(deprecated)]
+pub use settings::{DistanceFog, FogFalloff, FogNoise, FogSettings};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle};
+use bevy_color::{ColorToComponents, LinearRgba};
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_math::{ops, DVec3, Vec3, Vec4};
+use bevy_render::{
+    extract_component::ExtractComponentPlugin,
+    render_resource::{DynamicUniformBuffer, Shader, ShaderType},
+    renderer::{RenderDevice, RenderQueue},
+    view::ExtractedView,
+    Render, RenderApp, RenderSet,
+};
+use bevy_time::Time;
+
+/// The GPU-side representation of the fog configuration that's sent as a uniform to the shader
+#[derive(Copy, Clone, ShaderType, Default, Debug)]
+pub struct GpuFog {
+    /// Fog color
+    base_color: Vec4,
+    /// The color used for the fog where the view direction aligns with directional lights
+    directional_light_color: Vec4,
+    /// Allocated differently depending on fog mode.
+    /// See `fog.wgsl` for a detailed explanation
+    be: Vec3,
+    /// The exponent applied to the directional light alignment calculation
+    directional_light_exponent: f32,
+    /// Allocated differently depending on fog mode.
+    /// See `fog.wgsl` for a detailed explanation
+    bi: Vec3,
+    /// Unsigned int representation of the active fog falloff mode
+    mode: u32,
+    /// Offset of the noise field, accumulated from its velocity over time
+    noise_offset: Vec3,
+    /// Inverse of the noise scale, or zero if noise is disabled
+    noise_frequency: f32,
+    /// How strongly the noise modulates the fog density
+    noise_intensity: f32,
+    /// Distance from the view before which there's no fog
+    start_distance: f32,
+    /// Maximum opacity of the fog
+    max_opacity: f32,
+    /// Inscattered color for each view-sun angle, used by the aerial perspective mode.
+    /// See `fog.wgsl` for how it's indexed
+    aerial_perspective_lut: [Vec4; AERIAL_PERSPECTIVE_LUT_SIZE],
+}
+
+/// The number of view-sun angles the aerial perspective inscattering is precomputed for.
+///
+/// Important: This must be kept in sync with `fog.wgsl`
+pub const AERIAL_PERSPECTIVE_LUT_SIZE: usize = 32;
+
+// Important: These must be kept in sync with `fog.wgsl`, and `mesh_view_types.wgsl` in `bevy_pbr`
+const GPU_FOG_MODE_OFF: u32 = 0;
+const GPU_FOG_MODE_LINEAR: u32 = 1;
+const GPU_FOG_MODE_EXPONENTIAL: u32 = 2;
+const GPU_FOG_MODE_EXPONENTIAL_SQUARED: u32 = 3;
+const GPU_FOG_MODE_ATMOSPHERIC: u32 = 4;
+const GPU_FOG_MODE_EXPONENTIAL_HEIGHT: u32 = 5;
+const GPU_FOG_MODE_LINEAR_HEIGHT: u32 = 6;
+const GPU_FOG_MODE_AERIAL_PERSPECTIVE: u32 = 7;
+
+/// The number of noise lattice cells after which the fog noise field repeats, along each axis.
+///
+/// Important: This must be kept in sync with `fog.wgsl`
+const FOG_NOISE_PERIOD: f32 = 256.0;
+
+/// Metadata for fog
+#[derive(Default, Resource)]
+pub struct FogMeta {
+    pub gpu_fogs: DynamicUniformBuffer<GpuFog>,
+    /// The aerial perspective lookup table of each view, along with the parameters it was
+    /// computed from, so it's only recomputed when they change
+    aerial_perspective_luts: EntityHashMap<(
+        AerialPerspectiveLutParams,
+        [Vec4; AERIAL_PERSPECTIVE_LUT_SIZE],
+    )>,
+}
+
+/// Prepares fog metadata and writes the fog-related uniform buffers to the GPU
+pub fn prepare_fog(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut fog_meta: ResMut<FogMeta>,
+    time: Res<Time>,
+    views: Query<(Entity, Option<&DistanceFog>), With<ExtractedView>>,
+) {
+    let FogMeta {
+        gpu_fogs,
+        aerial_perspective_luts,
+    } = &mut *fog_meta;
+
+    aerial_perspective_luts.retain(|entity, _| views.contains(*entity));
+
+    let views_iter = views.iter();
+    let view_count = views_iter.len();
+    let Some(mut writer) = gpu_fogs.get_writer(view_count, &render_device, &render_queue) else {
+        return;
+    };
+    for (entity, fog) in views_iter {
+        let mut gpu_fog = if let Some(fog) = fog {
+            match &fog.falloff {
+                FogFalloff::Linear { start, end } => GpuFog {
+                    mode: GPU_FOG_MODE_LINEAR,
+                    base_color: LinearRgba::from(fog.color).to_vec4(),
+                    directional_light_color: LinearRgba::from(fog.directional_light_color)
+                        .to_vec4(),
+                    directional_light_exponent: fog.directional_light_exponent,
+                    be: Vec3::new(*start, *end, 0.0),
+                    ..Default::default()
+                },
+                FogFalloff::Exponential { density } => GpuFog {
+                    mode: GPU_FOG_MODE_EXPONENTIAL,
+                    base_color: LinearRgba::from(fog.color).to_vec4(),
+                    directional_light_color: LinearRgba::from(fog.directional_light_color)
+                        .to_vec4(),
+                    directional_light_exponent: fog.directional_light_exponent,
+                    be: Vec3::new(*density, 0.0, 0.0),
+                    ..Default::default()
+                },
+                FogFalloff::ExponentialSquared { density } => GpuFog {
+                    mode: GPU_FOG_MODE_EXPONENTIAL_SQUARED,
+                    base_color: LinearRgba::from(fog.color).to_vec4(),
+                    directional_light_color: LinearRgba::from(fog.directional_light_color)
+                        .to_vec4(),
+                    directional_light_exponent: fog.directional_light_exponent,
+                    be: Vec3::new(*density, 0.0, 0.0),
+                    ..Default::default()
+                },
+                FogFalloff::Atmospheric {
+                    extinction,
+                    inscattering,
+                } => GpuFog {
+                    mode: GPU_FOG_MODE_ATMOSPHERIC,
+                    base_color: LinearRgba::from(fog.color).to_vec4(),
+                    directional_light_color: LinearRgba::from(fog.directional_light_color)
+                        .to_vec4(),
+                    directional_light_exponent: fog.directional_light_exponent,
+                    be: *extinction,
+                    bi: *inscattering,
+                    ..Default::default()
+                },
+                FogFalloff::ExponentialHeight {
+                    density,
+                    height_falloff,
+                    base_height,
+                } => GpuFog {
+                    mode: GPU_FOG_MODE_EXPONENTIAL_HEIGHT,
+                    base_color: LinearRgba::from(fog.color).to_vec4(),
+                    directional_light_color: LinearRgba::from(fog.directional_light_color)
+                        .to_vec4(),
+                    directional_light_exponent: fog.directional_light_exponent,
+                    be: Vec3::new(*density, *height_falloff, *base_height),
+                    ..Default::default()
+                },
+                FogFalloff::LinearHeight {
+                    start,
+                    end,
+                    base_height,
+                    top_height,
+                } => GpuFog {
+                    mode: GPU_FOG_MODE_LINEAR_HEIGHT,
+                    base_color: LinearRgba::from(fog.color).to_vec4(),
+                    directional_light_color: LinearRgba::from(fog.directional_light_color)
+                        .to_vec4(),
+                    directional_light_exponent: fog.directional_light_exponent,
+                    // Inverted ranges are collapsed into hard cutoffs, see `FogFalloff::LinearHeight`
+                    be: Vec3::new(*start, end.max(*start), 0.0),
+                    bi: Vec3::new(*base_height, top_height.max(*base_height), 0.0),
+                    ..Default::default()
+                },
+                FogFalloff::AerialPerspective {
+                    sun_direction,
+                    sun_color,
+                    rayleigh_scattering,
+                    mie_scattering,
+                    mie_asymmetry,
+                } => {
+                    let params = AerialPerspectiveLutParams {
+                        sun_color: LinearRgba::from(*sun_color).to_vec3(),
+                        ambient_color: LinearRgba::from(fog.color).to_vec3(),
+                        rayleigh_scattering: *rayleigh_scattering,
+                        mie_scattering: *mie_scattering,
+                        mie_asymmetry: *mie_asymmetry,
+                    };
+                    let (cached_params, lut) = aerial_perspective_luts
+                        .entry(entity)
+                        .or_insert_with(|| (params, aerial_perspective_lut(&params)));
+                    if *cached_params != params {
+                        *cached_params = params;
+                        *lut = aerial_perspective_lut(&params);
+                    }
+
+                    GpuFog {
+                        mode: GPU_FOG_MODE_AERIAL_PERSPECTIVE,
+                        base_color: LinearRgba::from(fog.color).to_vec4(),
+                        be: aerial_perspective_extinction(*rayleigh_scattering, *mie_scattering),
+                        // The sun direction is only needed to look up the inscattering
+                        bi: sun_direction.normalize_or_zero(),
+                        aerial_perspective_lut: *lut,
+                        ..Default::default()
+                    }
+                }
+            }
+        } else {
+            // If no fog is added to a camera, by default it's off
+            GpuFog {
+                mode: GPU_FOG_MODE_OFF,
+                ..Default::default()
+            }
+        };
+
+        if let Some(fog) = fog {
+            gpu_fog.start_distance = fog.start_distance.max(0.0);
+            gpu_fog.max_opacity = fog.max_opacity.clamp(0.0, 1.0);
+        }
+
+        if let Some(noise) = fog.and_then(|fog| fog.noise) {
+            write_gpu_fog_noise(&mut gpu_fog, &noise, time.elapsed_seconds_f64());
+        }
+
+        // This is later read by the view bind group render commands of the 2d and 3d pipelines
+        commands.entity(entity).insert(ViewFogUniformOffset {
+            offset: writer.write(&gpu_fog),
+        });
+    }
+}
+
+/// Writes the [`FogNoise`] parameters to the [`GpuFog`], with the noise field drifted for
+/// `elapsed_seconds`.
+fn write_gpu_fog_noise(gpu_fog: &mut GpuFog, noise: &FogNoise, elapsed_seconds: f64) {
+    if noise.scale > 0.0 && noise.scale.is_finite() {
+        // Accumulated in double precision and wrapped to the period of the noise field, so the
+        // offset stays precise and continuous however long the app runs
+        let period = FOG_NOISE_PERIOD as f64 * noise.scale as f64;
+        gpu_fog.noise_offset = (noise.velocity.as_dvec3() * elapsed_seconds)
+            .rem_euclid(DVec3::splat(period))
+            .as_vec3();
+        gpu_fog.noise_frequency = noise.scale.recip();
+        gpu_fog.noise_intensity = noise.intensity.clamp(0.0, 1.0);
+    } else {
+        // A zero frequency disables the noise in `fog.wgsl`
+        gpu_fog.noise_offset = Vec3::ZERO;
+        gpu_fog.noise_frequency = 0.0;
+    }
+}
+
+/// The ratio of light extinguished (scattered or absorbed) by aerosols to light scattered by them
+const MIE_EXTINCTION_RATIO: f32 = 1.11;
+
+fn aerial_perspective_extinction(rayleigh_scattering: Vec3, mie_scattering: f32) -> Vec3 {
+    rayleigh_scattering + Vec3::splat(mie_scattering * MIE_EXTINCTION_RATIO)
+}
+
+/// The parameters an aerial perspective lookup table is computed from. The sun direction isn't
+/// one of them, since the table is indexed by the angle between the view direction and the sun.
+#[derive(Clone, Copy, PartialEq)]
+struct AerialPerspectiveLutParams {
+    sun_color: Vec3,
+    ambient_color: Vec3,
+    rayleigh_scattering: Vec3,
+    mie_scattering: f32,
+    mie_asymmetry: f32,
+}
+
+/// Returns the cosine of the angle between the view direction and the sun that the aerial
+/// perspective lookup table entry at `index` holds.
+///
+/// Entries are spaced so that angles close to the sun, where Mie scattering changes quickly,
+/// get more of them: entry `u` (normalized to the `0.0..=1.0` range) holds the angle whose cosine
+/// is `1.0 - 2.0 * (1.0 - u)²`. `fog.wgsl` inverts this mapping to look entries up.
+fn aerial_perspective_lut_cos_theta(index: usize) -> f32 {
+    let u = index as f32 / (AERIAL_PERSPECTIVE_LUT_SIZE - 1) as f32;
+    1.0 - 2.0 * (1.0 - u) * (1.0 - u)
+}
+
+/// Precomputes the color scattered towards the camera by a homogeneous atmosphere, per unit of
+/// extinguished light, for [`AERIAL_PERSPECTIVE_LUT_SIZE`] angles between the view direction
+/// and the sun.
+fn aerial_perspective_lut(
+    params: &AerialPerspectiveLutParams,
+) -> [Vec4; AERIAL_PERSPECTIVE_LUT_SIZE] {
+    use std::f32::consts::PI;
+
+    let extinction =
+        aerial_perspective_extinction(params.rayleigh_scattering, params.mie_scattering)
+            .max(Vec3::splat(f32::EPSILON));
+    let g = params.mie_asymmetry.clamp(-0.999, 0.999);
+    let scattering = params.rayleigh_scattering + Vec3::splat(params.mie_scattering);
+
+    core::array::from_fn(|i| {
+        let cos_theta = aerial_perspective_lut_cos_theta(i);
+
+        let rayleigh_phase = 3.0 / (16.0 * PI) * (1.0 + cos_theta * cos_theta);
+        // Henyey-Greenstein phase function
+        let mie_phase =
+            (1.0 - g * g) / (4.0 * PI * ops::powf(1.0 + g * g - 2.0 * g * cos_theta, 1.5));
+
+        // Phase functions are normalized so that isotropic scattering has a phase of `1.0`
+        let sun = params.sun_color
+            * (params.rayleigh_scattering * rayleigh_phase
+                + Vec3::splat(params.mie_scattering * mie_phase))
+            * (4.0 * PI);
+        let ambient = params.ambient_color * scattering;
+
+        ((sun + ambient) / extinction).extend(0.0)
+    })
+}
+
+/// Inserted on each `Entity` with an `ExtractedView` to keep track of its offset
+/// in the `gpu_fogs` `DynamicUniformBuffer` within `FogMeta`
+#[derive(Component)]
+pub struct ViewFogUniformOffset {
+    pub offset: u32,
+}
+
+/// Handle for the shared fog WGSL Shader internal asset, used by the 2d pipelines
+pub const FOG_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(9853837774633416344);
+
+/// A plugin that consolidates fog extraction, preparation and related resources/assets,
+/// shared by the 2d and 3d render pipelines
+pub struct FogPlugin;
+
+impl Plugin for FogPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, FOG_SHADER_HANDLE, "fog.wgsl", Shader::from_wgsl);
+
+        app.register_type::<DistanceFog>()
+            .register_type::<FogNoise>();
+        app.add_plugins(ExtractComponentPlugin::<DistanceFog>::default());
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<FogMeta>()
+                .add_systems(Render, prepare_fog.in_set(RenderSet::PrepareResources));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fog_noise_scale() {
+        for (scale, frequency) in [
+            (10.0, 0.1),
+            (0.0, 0.0),
+            (-1.0, 0.0),
+            (f32::NAN, 0.0),
+            (f32::INFINITY, 0.0),
+        ] {
+            let mut gpu_fog = GpuFog::default();
+            write_gpu_fog_noise(
+                &mut gpu_fog,
+                &FogNoise {
+                    scale,
+                    ..Default::default()
+                },
+                100.0,
+            );
+            assert_eq!(gpu_fog.noise_frequency, frequency);
+            assert!(gpu_fog.noise_offset.is_finite());
+        }
+    }
+
+    #[test]
+    fn aerial_perspective_lut_mapping() {
+        assert_eq!(aerial_perspective_lut_cos_theta(0), -1.0);
+        assert_eq!(
+            aerial_perspective_lut_cos_theta(AERIAL_PERSPECTIVE_LUT_SIZE - 1),
+            1.0
+        );
+
+        // The lookup in `fog.wgsl` must land back on each entry
+        for index in 0..AERIAL_PERSPECTIVE_LUT_SIZE {
+            let cos_theta = aerial_perspective_lut_cos_theta(index);
+            let lut_position = (1.0 - ((1.0 - cos_theta) * 0.5).clamp(0.0, 1.0).sqrt())
+                * (AERIAL_PERSPECTIVE_LUT_SIZE - 1) as f32;
+            assert!((lut_position - index as f32).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn aerial_perspective_lut_brightens_towards_sun() {
+        let lut = aerial_perspective_lut(&AerialPerspectiveLutParams {
+            sun_color: Vec3::ONE,
+            ambient_color: Vec3::ZERO,
+            rayleigh_scattering: FogFalloff::EARTH_RAYLEIGH_SCATTERING,
+            mie_scattering: FogFalloff::EARTH_MIE_SCATTERING,
+            mie_asymmetry: 0.8,
+        });
+
+        let sun_side = (0..AERIAL_PERSPECTIVE_LUT_SIZE)
+            .filter(|index| aerial_perspective_lut_cos_theta(*index) >= 0.0)
+            .map(|index| lut[index]);
+        for (previous, next) in sun_side.clone().zip(sun_side.skip(1)) {
+            assert!(next.x > previous.x && next.y > previous.y && next.z > previous.z);
+        }
+    }
+}
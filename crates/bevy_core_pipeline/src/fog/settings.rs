@@ -0,0 +1,723 @@
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_ecs::prelude::*;
+use bevy_math::{ops, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{extract_component::ExtractComponent, prelude::Camera};
+
+/// Configures the “classic” computer graphics [distance fog](https://en.wikipedia.org/wiki/Distance_fog) effect,
+/// in which objects appear progressively more covered in atmospheric haze the further away they are from the camera.
+/// Affects meshes rendered via the PBR `StandardMaterial`, as well as sprites and `ColorMaterial`
+/// meshes rendered by 2D cameras.
+///
+/// ## Falloff
+///
+/// The rate at which fog intensity increases with distance is controlled by the falloff mode.
+/// Currently, the following fog falloff modes are supported:
+///
+/// - [`FogFalloff::Linear`]
+/// - [`FogFalloff::Exponential`]
+/// - [`FogFalloff::ExponentialSquared`]
+/// - [`FogFalloff::Atmospheric`]
+/// - [`FogFalloff::ExponentialHeight`]
+/// - [`FogFalloff::LinearHeight`]
+/// - [`FogFalloff::AerialPerspective`]
+///
+/// ## Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::prelude::*;
+/// # use bevy_core_pipeline::{prelude::*, fog::{DistanceFog, FogFalloff}};
+/// # use bevy_color::Color;
+/// # fn system(mut commands: Commands) {
+/// commands.spawn((
+///     // Setup your camera as usual
+///     Camera3dBundle {
+///         // ... camera options
+/// #       ..Default::default()
+///     },
+///     // Add fog to the same entity
+///     DistanceFog {
+///         color: Color::WHITE,
+///         falloff: FogFalloff::Exponential { density: 1e-3 },
+///         ..Default::default()
+///     },
+/// ));
+/// # }
+/// # bevy_ecs::system::assert_is_system(system);
+/// ```
+///
+/// ## Material Override
+///
+/// Once enabled for a specific camera, the fog effect can also be disabled for individual
+/// `StandardMaterial` instances via the `fog_enabled` flag, or partially reduced via the
+/// `fog_influence` factor.
+///
+/// ## 2D
+///
+/// When added to a 2D camera, fog intensity is based on the view space depth of sprites and meshes
+/// (i.e. their distance along the camera's forward axis), so that layered parallax backgrounds fade
+/// consistently regardless of their position on screen. Directional light scattering is not
+/// applied in 2D.
+///
+/// 2D cameras sit far in front of the scene, at `z = 999.9` by default (see
+/// [`Camera2dBundle::new_with_far`](crate::core_2d::Camera2dBundle::new_with_far)), so a sprite
+/// at `z = 0.0` is `999.9` units away from the camera, and would be fully fogged by the default
+/// falloff. Use [`DistanceFog::start_distance`] to skip the empty space in front of the scene.
+/// For instance, for layers between `z = 0.0` (back) and `z = 100.0` (front):
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_core_pipeline::{prelude::*, fog::{DistanceFog, FogFalloff}};
+/// # use bevy_color::Color;
+/// # fn system(mut commands: Commands) {
+/// commands.spawn((
+///     Camera2dBundle::default(),
+///     DistanceFog {
+///         color: Color::srgb(0.5, 0.6, 0.7),
+///         // The front layer is `999.9 - 100.0` units away from the camera, and isn't fogged
+///         start_distance: 899.9,
+///         // The back layer is fully fogged
+///         falloff: FogFalloff::Linear {
+///             start: 0.0,
+///             end: 100.0,
+///         },
+///         ..Default::default()
+///     },
+/// ));
+/// # }
+/// # bevy_ecs::system::assert_is_system(system);
+/// ```
+#[derive(Debug, Clone, Component, Reflect, ExtractComponent)]
+#[extract_component_filter(With<Camera>)]
+#[reflect(Component, Default, Debug)]
+pub struct DistanceFog {
+    /// The color of the fog effect.
+    ///
+    /// **Tip:** The alpha channel of the color can be used to “modulate” the fog effect without
+    /// changing the fog falloff mode or parameters.
+    pub color: Color,
+
+    /// Color used to modulate the influence of directional light colors on the
+    /// fog, where the view direction aligns with each directional light direction,
+    /// producing a “glow” or light dispersion effect. (e.g. around the sun)
+    ///
+    /// Use [`Color::NONE`] to disable the effect.
+    pub directional_light_color: Color,
+
+    /// The exponent applied to the directional light alignment calculation.
+    /// A higher value means a more concentrated “glow”.
+    pub directional_light_exponent: f32,
+
+    /// Determines which falloff mode to use, and its parameters.
+    pub falloff: FogFalloff,
+
+    /// Distance from the camera, in world units, before which there's no fog at all.
+    ///
+    /// The falloff is evaluated for the distance past this point, so it offsets every falloff
+    /// mode. (e.g. for [`FogFalloff::Linear`], the fog starts at `start_distance + start`)
+    ///
+    /// Defaults to `0.0`.
+    pub start_distance: f32,
+
+    /// The maximum opacity of the fog, in the `0.0..=1.0` range, so that distant objects
+    /// never completely disappear.
+    ///
+    /// Defaults to `1.0`.
+    pub max_opacity: f32,
+
+    /// Optional animated 3D noise that modulates the fog density, so that the fog varies
+    /// spatially and temporally (e.g. drifting mist) instead of being perfectly uniform.
+    ///
+    /// Defaults to `None`.
+    pub noise: Option<FogNoise>,
+}
+
+/// Animated 3D noise modulation of the [`DistanceFog`] density.
+///
+/// The noise is sampled at a few points along the view ray and averaged, so it adds some cost
+/// per fragment when enabled.
+#[derive(Debug, Clone, Copy, Reflect)]
+#[reflect(Default, Debug)]
+pub struct FogNoise {
+    /// The approximate size of the noise features, in world units.
+    ///
+    /// Must be finite and greater than `0.0`. Otherwise, the noise is disabled.
+    pub scale: f32,
+
+    /// The velocity at which the noise field drifts through the world, in world units per second.
+    pub velocity: Vec3,
+
+    /// How strongly the noise modulates the fog density, from `0.0` (uniform fog) to `1.0`
+    /// (density varying between zero and twice its unmodulated value).
+    pub intensity: f32,
+}
+
+impl Default for FogNoise {
+    fn default() -> Self {
+        FogNoise {
+            scale: 10.0,
+            velocity: Vec3::new(0.5, 0.0, 0.25),
+            intensity: 0.5,
+        }
+    }
+}
+
+#[deprecated(since = "0.15.0", note = "Renamed to `DistanceFog`")]
+pub type FogSettings = DistanceFog;
+
+/// Allows switching between different fog falloff modes, and configuring their parameters.
+///
+/// ## Convenience Methods
+///
+/// When using non-linear fog modes it can be hard to determine the right parameter values
+/// for a given scene.
+///
+/// For easier artistic control, instead of creating the enum variants directly, you can use the
+/// visibility-based convenience methods:
+///
+/// - For `FogFalloff::Exponential`:
+///     - [`FogFalloff::from_visibility()`]
+///     - [`FogFalloff::from_visibility_contrast()`]
+///
+/// - For `FogFalloff::ExponentialSquared`:
+///     - [`FogFalloff::from_visibility_squared()`]
+///     - [`FogFalloff::from_visibility_contrast_squared()`]
+///
+/// - For `FogFalloff::Atmospheric`:
+///     - [`FogFalloff::from_visibility_color()`]
+///     - [`FogFalloff::from_visibility_colors()`]
+///     - [`FogFalloff::from_visibility_contrast_color()`]
+///     - [`FogFalloff::from_visibility_contrast_colors()`]
+///
+/// - For `FogFalloff::AerialPerspective`:
+///     - [`FogFalloff::from_sun_direction()`]
+#[derive(Debug, Clone, Reflect)]
+pub enum FogFalloff {
+    /// A linear fog falloff that grows in intensity between `start` and `end` distances.
+    ///
+    /// This falloff mode is simpler to control than other modes, however it can produce results that look “artificial”, depending on the scene.
+    ///
+    /// ## Formula
+    ///
+    /// The fog intensity for a given point in the scene is determined by the following formula:
+    ///
+    /// ```text
+    /// let fog_intensity = 1.0 - ((end - distance) / (end - start)).clamp(0.0, 1.0);
+    /// ```
+    ///
+    /// <svg width="370" height="212" viewBox="0 0 370 212" fill="none">
+    /// <title>Plot showing how linear fog falloff behaves for start and end values of 0.8 and 2.2, respectively.</title>
+    /// <path d="M331 151H42V49" stroke="currentColor" stroke-width="2"/>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-family="Inter" font-size="12" letter-spacing="0em"><tspan x="136" y="173.864">1</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-family="Inter" font-size="12" letter-spacing="0em"><tspan x="30" y="53.8636">1</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-family="Inter" font-size="12" letter-spacing="0em"><tspan x="42" y="173.864">0</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-family="Inter" font-size="12" letter-spacing="0em"><tspan x="232" y="173.864">2</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-family="Inter" font-size="12" letter-spacing="0em"><tspan x="332" y="173.864">3</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-family="Inter" font-size="12" letter-spacing="0em"><tspan x="161" y="190.864">distance</tspan></text>
+    /// <text font-family="sans-serif" transform="translate(10 132) rotate(-90)" fill="currentColor" style="white-space: pre" font-family="Inter" font-size="12" letter-spacing="0em"><tspan x="0" y="11.8636">fog intensity</tspan></text>
+    /// <path d="M43 150H117.227L263 48H331" stroke="#FF00E5"/>
+    /// <path d="M118 151V49" stroke="#FF00E5" stroke-dasharray="1 4"/>
+    /// <path d="M263 151V49" stroke="#FF00E5" stroke-dasharray="1 4"/>
+    /// <text font-family="sans-serif" fill="#FF00E5" style="white-space: pre" font-family="Inter" font-size="10" letter-spacing="0em"><tspan x="121" y="58.6364">start</tspan></text>
+    /// <text font-family="sans-serif" fill="#FF00E5" style="white-space: pre" font-family="Inter" font-size="10" letter-spacing="0em"><tspan x="267" y="58.6364">end</tspan></text>
+    /// </svg>
+    Linear {
+        /// Distance from the camera where fog is completely transparent, in world units.
+        start: f32,
+
+        /// Distance from the camera where fog is completely opaque, in world units.
+        end: f32,
+    },
+
+    /// An exponential fog falloff with a given `density`.
+    ///
+    /// Initially gains intensity quickly with distance, then more slowly. Typically produces more natural results than [`FogFalloff::Linear`],
+    /// but is a bit harder to control.
+    ///
+    /// To move the fog “further away”, use lower density values. To move it “closer” use higher density values.
+    ///
+    /// ## Tips
+    ///
+    /// - Use the [`FogFalloff::from_visibility()`] convenience method to create an exponential falloff with the proper
+    ///     density for a desired visibility distance in world units;
+    /// - It's not _unusual_ to have very large or very small values for the density, depending on the scene
+    ///     scale. Typically, for scenes with objects in the scale of thousands of units, you might want density values
+    ///     in the ballpark of `0.001`. Conversely, for really small scale scenes you might want really high values of
+    ///     density;
+    /// - Combine the `density` parameter with the [`DistanceFog`] `color`'s alpha channel for easier artistic control.
+    ///
+    /// ## Formula
+    ///
+    /// The fog intensity for a given point in the scene is determined by the following formula:
+    ///
+    /// ```text
+    /// let fog_intensity = 1.0 - 1.0 / (distance * density).exp();
+    /// ```
+    ///
+    /// <svg width="370" height="212" viewBox="0 0 370 212" fill="none">
+    /// <title>Plot showing how exponential fog falloff behaves for different density values</title>
+    /// <mask id="mask0_3_31" style="mask-type:alpha" maskUnits="userSpaceOnUse" x="42" y="42" width="286" height="108">
+    /// <rect x="42" y="42" width="286" height="108" fill="#D9D9D9"/>
+    /// </mask>
+    /// <g mask="url(#mask0_3_31)">
+    /// <path d="M42 150C42 150 98.3894 53 254.825 53L662 53" stroke="#FF003D" stroke-width="1"/>
+    /// <path d="M42 150C42 150 139.499 53 409.981 53L1114 53" stroke="#001AFF" stroke-width="1"/>
+    /// <path d="M42 150C42 150 206.348 53 662.281 53L1849 53" stroke="#14FF00" stroke-width="1"/>
+    /// </g>
+    /// <path d="M331 151H42V49" stroke="currentColor" stroke-width="2"/>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="136" y="173.864">1</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="30" y="53.8636">1</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="42" y="173.864">0</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="232" y="173.864">2</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="332" y="173.864">3</tspan></text>
+    /// <text font-family="sans-serif" fill="#FF003D" style="white-space: pre" font-size="10" letter-spacing="0em"><tspan x="77" y="64.6364">density = 2</tspan></text>
+    /// <text font-family="sans-serif" fill="#001AFF" style="white-space: pre" font-size="10" letter-spacing="0em"><tspan x="236" y="76.6364">density = 1</tspan></text>
+    /// <text font-family="sans-serif" fill="#14FF00" style="white-space: pre" font-size="10" letter-spacing="0em"><tspan x="205" y="115.636">density = 0.5</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="161" y="190.864">distance</tspan></text>
+    /// <text font-family="sans-serif" transform="translate(10 132) rotate(-90)" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="0" y="11.8636">fog intensity</tspan></text>
+    /// </svg>
+    Exponential {
+        /// Multiplier applied to the world distance (within the exponential fog falloff calculation).
+        density: f32,
+    },
+
+    /// A squared exponential fog falloff with a given `density`.
+    ///
+    /// Similar to [`FogFalloff::Exponential`], but grows more slowly in intensity for closer distances
+    /// before “catching up”.
+    ///
+    /// To move the fog “further away”, use lower density values. To move it “closer” use higher density values.
+    ///
+    /// ## Tips
+    ///
+    /// - Use the [`FogFalloff::from_visibility_squared()`] convenience method to create an exponential squared falloff
+    ///     with the proper density for a desired visibility distance in world units;
+    /// - Combine the `density` parameter with the [`DistanceFog`] `color`'s alpha channel for easier artistic control.
+    ///
+    /// ## Formula
+    ///
+    /// The fog intensity for a given point in the scene is determined by the following formula:
+    ///
+    /// ```text
+    /// let fog_intensity = 1.0 - 1.0 / (distance * density).squared().exp();
+    /// ```
+    ///
+    /// <svg width="370" height="212" viewBox="0 0 370 212" fill="none">
+    /// <title>Plot showing how exponential squared fog falloff behaves for different density values</title>
+    /// <mask id="mask0_1_3" style="mask-type:alpha" maskUnits="userSpaceOnUse" x="42" y="42" width="286" height="108">
+    /// <rect x="42" y="42" width="286" height="108" fill="#D9D9D9"/>
+    /// </mask>
+    /// <g mask="url(#mask0_1_3)">
+    /// <path d="M42 150C75.4552 150 74.9241 53.1724 166.262 53.1724L404 53.1724" stroke="#FF003D" stroke-width="1"/>
+    /// <path d="M42 150C107.986 150 106.939 53.1724 287.091 53.1724L756 53.1724" stroke="#001AFF" stroke-width="1"/>
+    /// <path d="M42 150C166.394 150 164.42 53.1724 504.035 53.1724L1388 53.1724" stroke="#14FF00" stroke-width="1"/>
+    /// </g>
+    /// <path d="M331 151H42V49" stroke="currentColor" stroke-width="2"/>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="136" y="173.864">1</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="30" y="53.8636">1</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="42" y="173.864">0</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="232" y="173.864">2</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="332" y="173.864">3</tspan></text>
+    /// <text font-family="sans-serif" fill="#FF003D" style="white-space: pre" font-size="10" letter-spacing="0em"><tspan x="61" y="54.6364">density = 2</tspan></text>
+    /// <text font-family="sans-serif" fill="#001AFF" style="white-space: pre" font-size="10" letter-spacing="0em"><tspan x="168" y="84.6364">density = 1</tspan></text>
+    /// <text font-family="sans-serif" fill="#14FF00" style="white-space: pre" font-size="10" letter-spacing="0em"><tspan x="174" y="121.636">density = 0.5</tspan></text>
+    /// <text font-family="sans-serif" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="161" y="190.864">distance</tspan></text>
+    /// <text font-family="sans-serif" transform="translate(10 132) rotate(-90)" fill="currentColor" style="white-space: pre" font-size="12" letter-spacing="0em"><tspan x="0" y="11.8636">fog intensity</tspan></text>
+    /// </svg>
+    ExponentialSquared {
+        /// Multiplier applied to the world distance (within the exponential squared fog falloff calculation).
+        density: f32,
+    },
+
+    /// A more general form of the [`FogFalloff::Exponential`] mode. The falloff formula is separated into
+    /// two terms, `extinction` and `inscattering`, for a somewhat simplified atmospheric scattering model.
+    /// Additionally, individual color channels can have their own density values, resulting in a total of
+    /// six different configuration parameters.
+    ///
+    /// ## Tips
+    ///
+    /// - Use the [`FogFalloff::from_visibility_colors()`] or [`FogFalloff::from_visibility_color()`] convenience methods
+    ///     to create an atmospheric falloff with the proper densities for a desired visibility distance in world units and
+    ///     extinction and inscattering colors;
+    /// - Combine the atmospheric fog parameters with the [`DistanceFog`] `color`'s alpha channel for easier artistic control.
+    ///
+    /// ## Formula
+    ///
+    /// Unlike other modes, atmospheric falloff doesn't use a simple intensity-based blend of fog color with
+    /// object color. Instead, it calculates per-channel extinction and inscattering factors, which are
+    /// then used to calculate the final color.
+    ///
+    /// ```text
+    /// let extinction_factor = 1.0 - 1.0 / (distance * extinction).exp();
+    /// let inscattering_factor = 1.0 - 1.0 / (distance * inscattering).exp();
+    /// let result = input_color * (1.0 - extinction_factor) + fog_color * inscattering_factor;
+    /// ```
+    ///
+    /// ## Equivalence to [`FogFalloff::Exponential`]
+    ///
+    /// For a density value of `D`, the following two falloff modes will produce identical visual results:
+    ///
+    /// ```
+    /// # use bevy_core_pipeline::fog::FogFalloff;
+    /// # use bevy_math::prelude::*;
+    /// # const D: f32 = 0.5;
+    /// #
+    /// let exponential = FogFalloff::Exponential {
+    ///     density: D,
+    /// };
+    ///
+    /// let atmospheric = FogFalloff::Atmospheric {
+    ///     extinction: Vec3::new(D, D, D),
+    ///     inscattering: Vec3::new(D, D, D),
+    /// };
+    /// ```
+    ///
+    /// **Note:** While the results are identical, [`FogFalloff::Atmospheric`] is computationally more expensive.
+    Atmospheric {
+        /// Controls how much light is removed due to atmospheric “extinction”, i.e. loss of light due to
+        /// photons being absorbed by atmospheric particles.
+        ///
+        /// Each component can be thought of as an independent per `R`/`G`/`B` channel `density` factor from
+        /// [`FogFalloff::Exponential`]: Multiplier applied to the world distance (within the fog
+        /// falloff calculation) for that specific channel.
+        ///
+        /// **Note:**
+        /// This value is not a `Color`, since it affects the channels exponentially in a non-intuitive way.
+        /// For artistic control, use the [`FogFalloff::from_visibility_colors()`] convenience method.
+        extinction: Vec3,
+
+        /// Controls how much light is added due to light scattering from the sun through the atmosphere.
+        ///
+        /// Each component can be thought of as an independent per `R`/`G`/`B` channel `density` factor from
+        /// [`FogFalloff::Exponential`]: A multiplier applied to the world distance (within the fog
+        /// falloff calculation) for that specific channel.
+        ///
+        /// **Note:**
+        /// This value is not a `Color`, since it affects the channels exponentially in a non-intuitive way.
+        /// For artistic control, use the [`FogFalloff::from_visibility_colors()`] convenience method.
+        inscattering: Vec3,
+    },
+
+    /// An exponential fog falloff whose density also decreases exponentially with height,
+    /// producing ground haze that thins out with altitude.
+    ///
+    /// At `base_height` (in world units, along the Y axis) the fog has the given `density`. Above it,
+    /// density is multiplied by `e^(-height_falloff * (height - base_height))`; below it, the fog
+    /// gets progressively denser.
+    ///
+    /// ## Tips
+    ///
+    /// - Use a `height_falloff` of `0.0` to get the same results as [`FogFalloff::Exponential`];
+    /// - Larger `height_falloff` values produce a thinner layer of fog close to `base_height`.
+    ///
+    /// ## Formula
+    ///
+    /// The fog density is integrated along the view ray, from the camera to the point in the scene:
+    ///
+    /// ```text
+    /// let height_delta = point_height - camera_height;
+    /// let camera_density = density * (-height_falloff * (camera_height - base_height)).exp();
+    /// let ray_factor = if (height_falloff * height_delta).abs() > 0.0001 {
+    ///     (1.0 - (-height_falloff * height_delta).exp()) / (height_falloff * height_delta)
+    /// } else {
+    ///     1.0
+    /// };
+    /// let fog_intensity = 1.0 - 1.0 / (distance * camera_density * ray_factor).exp();
+    /// ```
+    ExponentialHeight {
+        /// Multiplier applied to the world distance (within the exponential fog falloff calculation),
+        /// at `base_height`.
+        density: f32,
+
+        /// How quickly the fog density decreases with height, per world unit.
+        height_falloff: f32,
+
+        /// The height, in world units, at which the fog has exactly the given `density`.
+        base_height: f32,
+    },
+
+    /// A linear fog falloff (like [`FogFalloff::Linear`]) that is additionally faded out linearly with height.
+    ///
+    /// The fog is at full strength below `base_height`, and completely transparent above `top_height`
+    /// (both in world units, along the Y axis).
+    ///
+    /// `end` must not be less than `start`, and `top_height` must not be less than `base_height`.
+    /// Equal values produce a hard cutoff at that distance or height, instead of a gradual falloff.
+    ///
+    /// ## Formula
+    ///
+    /// The height factor is averaged along the view ray, from the camera to the point in the scene:
+    ///
+    /// ```text
+    /// let height_factor = |height: f32| ((top_height - height) / (top_height - base_height)).clamp(0.0, 1.0);
+    /// let distance_intensity = 1.0 - ((end - distance) / (end - start)).clamp(0.0, 1.0);
+    /// let fog_intensity = distance_intensity * average(height_factor, camera_height..point_height);
+    /// ```
+    LinearHeight {
+        /// Distance from the camera where fog is completely transparent, in world units.
+        start: f32,
+
+        /// Distance from the camera where fog is completely opaque, in world units.
+        end: f32,
+
+        /// Height below which the fog is at full strength, in world units.
+        base_height: f32,
+
+        /// Height above which there is no fog, in world units.
+        top_height: f32,
+    },
+
+    /// A physically-plausible aerial perspective falloff, simulating light being scattered by air
+    /// molecules (Rayleigh scattering) and larger particles such as dust and water droplets
+    /// (Mie scattering) between the camera and distant objects.
+    ///
+    /// Distant objects are both dimmed and shifted towards the color of the sky, which depends on
+    /// the angle between the view direction and the sun: bluish when looking away from the sun,
+    /// and a brighter, whiter haze when looking towards it.
+    ///
+    /// The amount of light scattered towards the camera for each view angle is precomputed into
+    /// a small lookup table from the sun direction and the atmosphere parameters, so this mode is
+    /// only slightly more expensive than [`FogFalloff::Atmospheric`].
+    ///
+    /// The [`DistanceFog`] `color` is used as the ambient sky light, scattered equally in all
+    /// directions, and its alpha channel modulates the whole effect. The `directional_light_color`
+    /// and `directional_light_exponent` are ignored, since the glow around the sun is already
+    /// part of the Mie scattering.
+    ///
+    /// ## Tips
+    ///
+    /// - Use the [`FogFalloff::from_sun_direction()`] convenience method to create an aerial
+    ///     perspective falloff with Earth-like atmosphere parameters, for scenes in meters;
+    /// - Keep the `sun_direction` and `sun_color` in sync with the scene's `DirectionalLight`, if any;
+    /// - Scale the scattering coefficients up for smaller scenes, or to get a hazier atmosphere.
+    ///
+    /// ## Formula
+    ///
+    /// The atmosphere is assumed to be homogeneous, so extinction is computed per channel like in
+    /// [`FogFalloff::Atmospheric`], while the inscattered color is read from the lookup table:
+    ///
+    /// ```text
+    /// let extinction = rayleigh_scattering + mie_scattering * 1.11;
+    /// let transmittance = (-distance * extinction).exp();
+    /// let result = input_color * transmittance + inscattered_color(view_sun_angle) * (1.0 - transmittance);
+    /// ```
+    AerialPerspective {
+        /// The direction towards the sun, in world space.
+        sun_direction: Vec3,
+
+        /// The color of the sunlight.
+        sun_color: Color,
+
+        /// Per-channel scattering coefficient of air molecules, per world unit.
+        ///
+        /// Shorter (blue) wavelengths scatter more, which is what gives distant objects their
+        /// bluish tint.
+        rayleigh_scattering: Vec3,
+
+        /// Scattering coefficient of aerosols (dust, water droplets, etc.), per world unit.
+        ///
+        /// Aerosols also absorb some light, so the resulting extinction is slightly higher.
+        mie_scattering: f32,
+
+        /// How much light scattered by aerosols is biased in the forward direction, in the
+        /// `-1.0..1.0` range. Higher values produce a tighter, brighter haze around the sun.
+        mie_asymmetry: f32,
+    },
+}
+
+impl FogFalloff {
+    /// Creates a [`FogFalloff::Exponential`] value from the given visibility distance in world units,
+    /// using the revised Koschmieder contrast threshold, [`FogFalloff::REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD`].
+    pub fn from_visibility(visibility: f32) -> FogFalloff {
+        FogFalloff::from_visibility_contrast(
+            visibility,
+            FogFalloff::REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD,
+        )
+    }
+
+    /// Creates a [`FogFalloff::Exponential`] value from the given visibility distance in world units,
+    /// and a given contrast threshold in the range of `0.0` to `1.0`.
+    pub fn from_visibility_contrast(visibility: f32, contrast_threshold: f32) -> FogFalloff {
+        FogFalloff::Exponential {
+            density: FogFalloff::koschmieder(visibility, contrast_threshold),
+        }
+    }
+
+    /// Creates a [`FogFalloff::ExponentialSquared`] value from the given visibility distance in world units,
+    /// using the revised Koschmieder contrast threshold, [`FogFalloff::REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD`].
+    pub fn from_visibility_squared(visibility: f32) -> FogFalloff {
+        FogFalloff::from_visibility_contrast_squared(
+            visibility,
+            FogFalloff::REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD,
+        )
+    }
+
+    /// Creates a [`FogFalloff::ExponentialSquared`] value from the given visibility distance in world units,
+    /// and a given contrast threshold in the range of `0.0` to `1.0`.
+    pub fn from_visibility_contrast_squared(
+        visibility: f32,
+        contrast_threshold: f32,
+    ) -> FogFalloff {
+        FogFalloff::ExponentialSquared {
+            density: (FogFalloff::koschmieder(visibility, contrast_threshold) / visibility).sqrt(),
+        }
+    }
+
+    /// Creates a [`FogFalloff::Atmospheric`] value from the given visibility distance in world units,
+    /// and a shared color for both extinction and inscattering, using the revised Koschmieder contrast threshold,
+    /// [`FogFalloff::REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD`].
+    pub fn from_visibility_color(
+        visibility: f32,
+        extinction_inscattering_color: Color,
+    ) -> FogFalloff {
+        FogFalloff::from_visibility_contrast_colors(
+            visibility,
+            FogFalloff::REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD,
+            extinction_inscattering_color,
+            extinction_inscattering_color,
+        )
+    }
+
+    /// Creates a [`FogFalloff::Atmospheric`] value from the given visibility distance in world units,
+    /// extinction and inscattering colors, using the revised Koschmieder contrast threshold,
+    /// [`FogFalloff::REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD`].
+    ///
+    /// ## Tips
+    /// - Alpha values of the provided colors can modulate the `extinction` and `inscattering` effects;
+    /// - Using an `extinction_color` of [`Color::WHITE`] or [`Color::NONE`] disables the extinction effect;
+    /// - Using an `inscattering_color` of [`Color::BLACK`] or [`Color::NONE`] disables the inscattering effect.
+    pub fn from_visibility_colors(
+        visibility: f32,
+        extinction_color: Color,
+        inscattering_color: Color,
+    ) -> FogFalloff {
+        FogFalloff::from_visibility_contrast_colors(
+            visibility,
+            FogFalloff::REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD,
+            extinction_color,
+            inscattering_color,
+        )
+    }
+
+    /// Creates a [`FogFalloff::Atmospheric`] value from the given visibility distance in world units,
+    /// a contrast threshold in the range of `0.0` to `1.0`, and a shared color for both extinction and inscattering.
+    pub fn from_visibility_contrast_color(
+        visibility: f32,
+        contrast_threshold: f32,
+        extinction_inscattering_color: Color,
+    ) -> FogFalloff {
+        FogFalloff::from_visibility_contrast_colors(
+            visibility,
+            contrast_threshold,
+            extinction_inscattering_color,
+            extinction_inscattering_color,
+        )
+    }
+
+    /// Creates a [`FogFalloff::Atmospheric`] value from the given visibility distance in world units,
+    /// a contrast threshold in the range of `0.0` to `1.0`, extinction and inscattering colors.
+    ///
+    /// ## Tips
+    /// - Alpha values of the provided colors can modulate the `extinction` and `inscattering` effects;
+    /// - Using an `extinction_color` of [`Color::WHITE`] or [`Color::NONE`] disables the extinction effect;
+    /// - Using an `inscattering_color` of [`Color::BLACK`] or [`Color::NONE`] disables the inscattering effect.
+    pub fn from_visibility_contrast_colors(
+        visibility: f32,
+        contrast_threshold: f32,
+        extinction_color: Color,
+        inscattering_color: Color,
+    ) -> FogFalloff {
+        use std::f32::consts::E;
+
+        let [r_e, g_e, b_e, a_e] = LinearRgba::from(extinction_color).to_f32_array();
+        let [r_i, g_i, b_i, a_i] = LinearRgba::from(inscattering_color).to_f32_array();
+
+        FogFalloff::Atmospheric {
+            extinction: Vec3::new(
+                // Values are subtracted from 1.0 here to preserve the intuitive/artistic meaning of
+                // colors, since they're later subtracted. (e.g. by giving a blue extinction color, you
+                // get blue and _not_ yellow results)
+                ops::powf(1.0 - r_e, E),
+                ops::powf(1.0 - g_e, E),
+                ops::powf(1.0 - b_e, E),
+            ) * FogFalloff::koschmieder(visibility, contrast_threshold)
+                * ops::powf(a_e, E),
+
+            inscattering: Vec3::new(ops::powf(r_i, E), ops::powf(g_i, E), ops::powf(b_i, E))
+                * FogFalloff::koschmieder(visibility, contrast_threshold)
+                * ops::powf(a_i, E),
+        }
+    }
+
+    /// Creates a [`FogFalloff::AerialPerspective`] value from the given direction towards the sun,
+    /// using Earth-like atmosphere parameters at sea level, for scenes where one world unit is
+    /// one meter.
+    pub fn from_sun_direction(sun_direction: Vec3) -> FogFalloff {
+        FogFalloff::AerialPerspective {
+            sun_direction,
+            sun_color: Color::WHITE,
+            rayleigh_scattering: FogFalloff::EARTH_RAYLEIGH_SCATTERING,
+            mie_scattering: FogFalloff::EARTH_MIE_SCATTERING,
+            mie_asymmetry: 0.8,
+        }
+    }
+
+    /// Rayleigh scattering coefficients of Earth's atmosphere at sea level, per meter,
+    /// for the red, green and blue channels.
+    pub const EARTH_RAYLEIGH_SCATTERING: Vec3 = Vec3::new(5.802e-6, 13.558e-6, 33.1e-6);
+
+    /// Mie scattering coefficient of Earth's atmosphere at sea level, per meter.
+    pub const EARTH_MIE_SCATTERING: f32 = 3.996e-6;
+
+    /// A 2% contrast threshold was originally proposed by Koschmieder, being the
+    /// minimum visual contrast at which a human observer could detect an object.
+    /// We use a revised 5% contrast threshold, deemed more realistic for typical human observers.
+    pub const REVISED_KOSCHMIEDER_CONTRAST_THRESHOLD: f32 = 0.05;
+
+    /// Calculates the extinction coefficient β, from V and Cₜ, where:
+    ///
+    /// - Cₜ is the contrast threshold, in the range of `0.0` to `1.0`
+    /// - V is the visibility distance in which a perfectly black object is still identifiable
+    ///   against the horizon sky within the contrast threshold
+    ///
+    /// We start with Koschmieder's equation:
+    ///
+    /// ```text
+    ///       -ln(Cₜ)
+    ///  V = ─────────
+    ///          β
+    /// ```
+    ///
+    /// Multiplying both sides by β/V, that gives us:
+    ///
+    /// ```text
+    ///       -ln(Cₜ)
+    ///  β = ─────────
+    ///          V
+    /// ```
+    ///
+    /// See:
+    /// - <https://en.wikipedia.org/wiki/Visibility>
+    /// - <https://www.biral.com/wp-content/uploads/2015/02/Introduction_to_visibility-v2-2.pdf>
+    pub fn koschmieder(v: f32, c_t: f32) -> f32 {
+        -ops::ln(c_t) / v
+    }
+}
+
+impl Default for DistanceFog {
+    fn default() -> Self {
+        DistanceFog {
+            color: Color::WHITE,
+            falloff: FogFalloff::Linear {
+                start: 0.0,
+                end: 100.0,
+            },
+            directional_light_color: Color::NONE,
+            directional_light_exponent: 8.0,
+            start_distance: 0.0,
+            max_opacity: 1.0,
+            noise: None,
+        }
+    }
+}
@@ -15,6 +15,7 @@ pub mod core_2d;
 pub mod core_3d;
 pub mod deferred;
 pub mod dof;
+pub mod fog;
 pub mod fullscreen_vertex_shader;
 pub mod fxaa;
 pub mod motion_blur;
@@ -61,6 +62,7 @@ use crate::{
     core_3d::Core3dPlugin,
     deferred::copy_lighting_id::CopyDeferredLightingIdPlugin,
     dof::DepthOfFieldPlugin,
+    fog::FogPlugin,
     fullscreen_vertex_shader::FULLSCREEN_SHADER_HANDLE,
     fxaa::FxaaPlugin,
     motion_blur::MotionBlurPlugin,
@@ -104,6 +106,7 @@ impl Plugin for CorePipelinePlugin {
                 CasPlugin,
                 MotionBlurPlugin,
                 DepthOfFieldPlugin,
+                FogPlugin,
                 SmaaPlugin,
                 PostProcessingPlugin,
             ));
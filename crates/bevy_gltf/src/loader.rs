@@ -17,7 +17,7 @@ use bevy_hierarchy::{BuildChildren, ChildBuild, WorldChildBuilder};
 use bevy_math::{Affine2, Mat4, Vec3};
 use bevy_pbr::{
     DirectionalLight, DirectionalLightBundle, PbrBundle, PointLight, PointLightBundle, SpotLight,
-    SpotLightBundle, StandardMaterial, UvChannel, MAX_JOINTS,
+    SpotLightBundle, StandardMaterial, UvChannel,
 };
 use bevy_render::{
     alpha::AlphaMode,
@@ -1726,21 +1726,8 @@ impl<'a> GltfTreeIterator<'a> {
             .collect::<HashMap<_, _>>();
 
         let mut nodes = Vec::new();
-        let mut warned_about_max_joints = HashSet::new();
         while let Some(index) = empty_children.pop_front() {
             if let Some(skin) = unprocessed_nodes.get(&index).unwrap().0.skin() {
-                if skin.joints().len() > MAX_JOINTS && warned_about_max_joints.insert(skin.index())
-                {
-                    warn!(
-                        "The glTF skin {:?} has {} joints, but the maximum supported is {}",
-                        skin.name()
-                            .map(ToString::to_string)
-                            .unwrap_or_else(|| skin.index().to_string()),
-                        skin.joints().len(),
-                        MAX_JOINTS
-                    );
-                }
-
                 let skin_has_dependencies = skin
                     .joints()
                     .any(|joint| unprocessed_nodes.contains_key(&joint.index()));
@@ -10,7 +10,10 @@ use bevy_render::{
     renderer::RenderDevice,
 };
 
-use crate::{Material, MaterialPipeline, MaterialPipelineKey, MeshPipeline, MeshPipelineKey};
+use crate::{
+    Material, MaterialPipeline, MaterialPipelineKey, MeshPipeline, MeshPipelineKey,
+    SpecularTransmissionReach,
+};
 
 pub struct MaterialExtensionPipeline {
     pub mesh_pipeline: MeshPipeline,
@@ -117,6 +120,13 @@ pub trait MaterialExtension: Asset + AsBindGroup + Clone + Sized {
 /// If the extension `E` returns a non-default result from `fragment_shader()` it will be used in place of the base
 /// fragment shader.
 ///
+/// The same applies to the prepass and deferred shaders, so a vertex shader animating the mesh (e.g. for wind) should
+/// usually be returned from `prepass_vertex_shader()` and `deferred_vertex_shader()` as well, to keep depth and
+/// shadows consistent with the main pass.
+///
+/// The pipeline is specialized by the base material first, then by [`MaterialExtension::specialize`], which can
+/// further customize the [`RenderPipelineDescriptor`], e.g. to add shader defs or change the primitive state.
+///
 /// When used with `StandardMaterial` as the base, all the standard material fields are
 /// present, so the `pbr_fragment` shader functions can be called from the extension shader (see
 /// the `extended_material` example).
@@ -214,6 +224,14 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         B::reads_view_transmission_texture(&self.base)
     }
 
+    fn specular_transmission_steps(&self) -> Option<usize> {
+        B::specular_transmission_steps(&self.base)
+    }
+
+    fn specular_transmission_reach(&self) -> Option<SpecularTransmissionReach> {
+        B::specular_transmission_reach(&self.base)
+    }
+
     fn prepass_vertex_shader() -> ShaderRef {
         match E::prepass_vertex_shader() {
             ShaderRef::Default => B::prepass_vertex_shader(),
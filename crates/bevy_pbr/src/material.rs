@@ -7,8 +7,9 @@ use crate::*;
 use bevy_asset::{Asset, AssetId, AssetServer};
 use bevy_core_pipeline::{
     core_3d::{
-        AlphaMask3d, Camera3d, Opaque3d, Opaque3dBinKey, ScreenSpaceTransmissionQuality,
-        Transmissive3d, Transparent3d,
+        AlphaMask3d, Camera3d, Opaque3d, Opaque3dBinKey, ScreenSpaceTransmissionBlur,
+        ScreenSpaceTransmissionQuality, Transmissive3d, Transparent3d,
+        TRANSMISSION_MIP_CHAIN_SUPPORTED,
     },
     prepass::{
         DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass, OpaqueNoLightmap3dBinKey,
@@ -20,13 +21,16 @@ use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::SRes, SystemParamItem},
 };
+use bevy_math::{Mat4, Rect, Vec2, Vec3, Vec4Swizzles};
 use bevy_reflect::std_traits::ReflectDefault;
 use bevy_reflect::Reflect;
 use bevy_render::{
+    batching::gpu_preprocessing,
     camera::TemporalJitter,
     extract_instances::{ExtractInstancesPlugin, ExtractedInstances},
     extract_resource::ExtractResource,
     mesh::{MeshVertexBufferLayoutRef, RenderMesh},
+    primitives::Sphere,
     render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
     render_phase::*,
     render_resource::*,
@@ -144,6 +148,29 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
         false
     }
 
+    /// Returns the number of steps to use for this material in the [`Transmissive3d`] pass, overriding
+    /// [`Camera3d::screen_space_specular_transmission_steps`]. Transmissive meshes behind and overlapping
+    /// a mesh using this material use at least this many steps as well.
+    ///
+    /// Only used if [`Material::reads_view_transmission_texture`] returns `true`.
+    #[inline]
+    fn specular_transmission_steps(&self) -> Option<usize> {
+        None
+    }
+
+    /// Returns how far the background seen through this material can be offset and blurred, or [`None`]
+    /// if unknown.
+    ///
+    /// Used to estimate the screen space area read by each mesh in the [`Transmissive3d`] pass, so that meshes
+    /// that don't read each other's output can share a step. If [`None`], meshes using this material are assumed
+    /// to read from the whole screen.
+    ///
+    /// Only used if [`Material::reads_view_transmission_texture`] returns `true`.
+    #[inline]
+    fn specular_transmission_reach(&self) -> Option<SpecularTransmissionReach> {
+        None
+    }
+
     /// Returns this material's prepass vertex shader. If [`ShaderRef::Default`] is returned, the default prepass vertex shader
     /// will be used.
     ///
@@ -499,7 +526,7 @@ pub const fn tonemapping_pipeline_key(tonemapping: Tonemapping) -> MeshPipelineK
     }
 }
 
-pub const fn screen_space_specular_transmission_pipeline_key(
+pub fn screen_space_specular_transmission_pipeline_key(
     screen_space_transmissive_blur_quality: ScreenSpaceTransmissionQuality,
 ) -> MeshPipelineKey {
     match screen_space_transmissive_blur_quality {
@@ -515,9 +542,99 @@ pub const fn screen_space_specular_transmission_pipeline_key(
         ScreenSpaceTransmissionQuality::Ultra => {
             MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_ULTRA
         }
+        ScreenSpaceTransmissionQuality::Custom {
+            taps,
+            spiral_turns,
+            fractional_jitter,
+        } => MeshPipelineKey::from_screen_space_specular_transmission_custom(
+            taps,
+            spiral_turns,
+            fractional_jitter,
+        ),
     }
 }
 
+/// Upper bounds on how far the background seen through a transmissive [`Material`] can be offset and
+/// blurred. See [`Material::specular_transmission_reach`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpecularTransmissionReach {
+    /// The maximum distance refraction can offset the background from the surface, in the local
+    /// units of the mesh. It's scaled by the mesh's world space scale, as the material thickness is.
+    pub thickness: f32,
+    /// The maximum perceptual roughness, which controls the radius of the background blur.
+    pub perceptual_roughness: f32,
+}
+
+/// Computes a conservative screen space rectangle (in normalized device coordinates) covering the
+/// area a transmissive mesh with the given world space bounds draws to and reads from, or [`None`]
+/// if the area crosses the camera plane.
+///
+/// The bounding sphere is grown by how far refraction can offset the background, and the rectangle
+/// by the radius of the blur applied to it (see `pbr_transmission.wgsl`).
+fn transmissive_screen_space_bounds(
+    view: &ExtractedView,
+    clip_from_world: &Mat4,
+    rangefinder: &ViewRangefinder3d,
+    world_bounds: MeshWorldBounds,
+    reach: SpecularTransmissionReach,
+) -> Option<Rect> {
+    // The thickness is scaled by the mesh's world space scale in `pbr_fragment.wgsl`, which is at
+    // most its largest axis scale
+    let reach_bounds = Sphere {
+        center: world_bounds.sphere.center,
+        radius: world_bounds.sphere.radius + reach.thickness.max(0.0) * world_bounds.max_scale,
+    };
+
+    let min_depth =
+        -rangefinder.distance_translation(&Vec3::from(reach_bounds.center)) - reach_bounds.radius;
+    if min_depth <= 0.0 {
+        return None;
+    }
+
+    let mut bounds = screen_space_bounds(clip_from_world, &reach_bounds)?;
+
+    // The blur's radius is `roughness² / view_z` in UV units, scaled by the aspect ratio vertically.
+    // Doubled to also cover the texels read when sampling a blurred mip level instead, then
+    // converted to NDC.
+    let viewport_size = view.viewport.zw().as_vec2().max(Vec2::ONE);
+    let aspect = viewport_size.x / viewport_size.y;
+    let blur_radius =
+        2.0 * reach.perceptual_roughness * reach.perceptual_roughness / min_depth * aspect.max(1.0);
+    let blur_extent = 2.0 * Vec2::new(blur_radius / aspect, blur_radius);
+    bounds.min -= blur_extent;
+    bounds.max += blur_extent;
+
+    Some(bounds)
+}
+
+/// Computes a conservative screen space rectangle (in normalized device coordinates) covering the
+/// given world space bounding sphere, or [`None`] if the sphere crosses the camera plane.
+fn screen_space_bounds(clip_from_world: &Mat4, world_bounds: &Sphere) -> Option<Rect> {
+    let center = Vec3::from(world_bounds.center);
+    let mut bounds = Rect {
+        min: Vec2::INFINITY,
+        max: Vec2::NEG_INFINITY,
+    };
+
+    // Project the corners of the cube enclosing the sphere
+    for i in 0..8 {
+        let corner_offset = Vec3::new(
+            if i & 1 == 0 { -1.0 } else { 1.0 },
+            if i & 2 == 0 { -1.0 } else { 1.0 },
+            if i & 4 == 0 { -1.0 } else { 1.0 },
+        ) * world_bounds.radius;
+        let clip_position = *clip_from_world * (center + corner_offset).extend(1.0);
+        if clip_position.w <= 0.0 {
+            return None;
+        }
+        let ndc_position = clip_position.xy() / clip_position.w;
+        bounds.min = bounds.min.min(ndc_position);
+        bounds.max = bounds.max.max(ndc_position);
+    }
+
+    Some(bounds)
+}
+
 /// For each view, iterates over all the meshes visible from that view and adds
 /// them to [`BinnedRenderPhase`]s or [`SortedRenderPhase`]s as appropriate.
 #[allow(clippy::too_many_arguments)]
@@ -539,6 +656,9 @@ pub fn queue_material_meshes<M: Material>(
     render_meshes: Res<RenderAssets<RenderMesh>>,
     render_materials: Res<RenderAssets<PreparedMaterial<M>>>,
     render_mesh_instances: Res<RenderMeshInstances>,
+    gpu_batched_instance_buffers: Option<
+        Res<gpu_preprocessing::BatchedInstanceBuffers<MeshUniform, MeshInputUniform>>,
+    >,
     render_material_instances: Res<RenderMaterialInstances<M>>,
     render_lightmaps: Res<RenderLightmaps>,
     render_visibility_ranges: Res<RenderVisibilityRanges>,
@@ -572,6 +692,12 @@ pub fn queue_material_meshes<M: Material>(
 ) where
     M::Data: PartialEq + Eq + Hash + Clone,
 {
+    // Only needed to compute the world space bounds of transmissive meshes when using GPU mesh
+    // instance data building, see `RenderMeshInstances::world_bounds`
+    let mesh_input_uniforms = gpu_batched_instance_buffers
+        .as_ref()
+        .map_or(&[][..], |buffers| buffers.current_input_buffer.values());
+
     for (
         view_entity,
         view,
@@ -674,9 +800,18 @@ pub fn queue_material_meshes<M: Material>(
             view_key |= screen_space_specular_transmission_pipeline_key(
                 camera_3d.screen_space_specular_transmission_quality,
             );
+            if TRANSMISSION_MIP_CHAIN_SUPPORTED
+                && camera_3d.screen_space_specular_transmission_blur
+                    == ScreenSpaceTransmissionBlur::MipChain
+            {
+                view_key |= MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_MIP_CHAIN;
+            }
         }
 
         let rangefinder = view.rangefinder3d();
+        let clip_from_world = view.clip_from_world.unwrap_or_else(|| {
+            view.clip_from_view * view.world_from_view.compute_matrix().inverse()
+        });
         for visible_entity in visible_entities.iter::<WithMesh>() {
             let Some(material_asset_id) = render_material_instances.get(visible_entity) else {
                 continue;
@@ -754,6 +889,8 @@ pub fn queue_material_meshes<M: Material>(
                 .intersection(MeshPipelineKey::BLEND_RESERVED_BITS | MeshPipelineKey::MAY_DISCARD)
             {
                 MeshPipelineKey::BLEND_OPAQUE | MeshPipelineKey::BLEND_ALPHA_TO_COVERAGE => {
+                    // Materials reading from the `ViewTransmissionTexture` are always rendered forward in the
+                    // `Transmissive3d` phase, even on deferred cameras, where it runs after the deferred lighting pass
                     if material.properties.reads_view_transmission_texture {
                         let distance = rangefinder.distance_translation(&mesh_instance.translation)
                             + material.properties.depth_bias;
@@ -764,6 +901,22 @@ pub fn queue_material_meshes<M: Material>(
                             distance,
                             batch_range: 0..1,
                             extra_index: PhaseItemExtraIndex::NONE,
+                            specular_transmission_steps: material
+                                .properties
+                                .specular_transmission_steps,
+                            screen_space_bounds: material
+                                .properties
+                                .specular_transmission_reach
+                                .and_then(|reach| {
+                                    transmissive_screen_space_bounds(
+                                        view,
+                                        &clip_from_world,
+                                        &rangefinder,
+                                        render_mesh_instances
+                                            .world_bounds(*visible_entity, mesh_input_uniforms)?,
+                                        reach,
+                                    )
+                                }),
                         });
                     } else if material.properties.render_method == OpaqueRendererMethod::Forward {
                         let bin_key = Opaque3dBinKey {
@@ -792,6 +945,22 @@ pub fn queue_material_meshes<M: Material>(
                             distance,
                             batch_range: 0..1,
                             extra_index: PhaseItemExtraIndex::NONE,
+                            specular_transmission_steps: material
+                                .properties
+                                .specular_transmission_steps,
+                            screen_space_bounds: material
+                                .properties
+                                .specular_transmission_reach
+                                .and_then(|reach| {
+                                    transmissive_screen_space_bounds(
+                                        view,
+                                        &clip_from_world,
+                                        &rangefinder,
+                                        render_mesh_instances
+                                            .world_bounds(*visible_entity, mesh_input_uniforms)?,
+                                        reach,
+                                    )
+                                }),
                         });
                     } else if material.properties.render_method == OpaqueRendererMethod::Forward {
                         let bin_key = OpaqueNoLightmap3dBinKey {
@@ -894,6 +1063,10 @@ pub struct MaterialProperties {
     /// This allows taking color output from the [`Opaque3d`] pass as an input, (for screen-space transmission) but requires
     /// rendering to take place in a separate [`Transmissive3d`] pass.
     pub reads_view_transmission_texture: bool,
+    /// Overrides the number of steps used for this material in the [`Transmissive3d`] pass.
+    pub specular_transmission_steps: Option<usize>,
+    /// How far the background seen through this material can be offset and blurred, if known.
+    pub specular_transmission_reach: Option<SpecularTransmissionReach>,
 }
 
 /// Data prepared for a [`Material`] instance.
@@ -941,6 +1114,8 @@ impl<M: Material> RenderAsset for PreparedMaterial<M> {
                         reads_view_transmission_texture: mesh_pipeline_key_bits
                             .contains(MeshPipelineKey::READS_VIEW_TRANSMISSION_TEXTURE),
                         render_method: method,
+                        specular_transmission_steps: material.specular_transmission_steps(),
+                        specular_transmission_reach: material.specular_transmission_reach(),
                         mesh_pipeline_key_bits,
                     },
                 })
@@ -1003,3 +1178,97 @@ impl<T: Material> PreparedMaterial<T> {
         MaterialBindGroupId(Some(self.bind_group.id()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::{Affine3, Affine3A, Quat, UVec4};
+    use bevy_render::{primitives::Aabb, view::ColorGrading};
+    use bevy_transform::components::GlobalTransform;
+
+    #[test]
+    fn screen_space_specular_transmission_custom_key() {
+        let key = screen_space_specular_transmission_pipeline_key(
+            ScreenSpaceTransmissionQuality::Custom {
+                taps: 12,
+                spiral_turns: 3,
+                fractional_jitter: 0.5,
+            },
+        );
+        assert_eq!(
+            key.screen_space_specular_transmission_custom(),
+            Some((12, 3, 8))
+        );
+    }
+
+    #[test]
+    fn transmissive_bounds_scale_thickness() {
+        let entity = Entity::from_raw(0);
+        let mut instances = RenderMeshInstancesCpu::default();
+        instances.insert(
+            entity,
+            RenderMeshInstanceCpu {
+                shared: RenderMeshInstanceShared {
+                    mesh_asset_id: AssetId::default(),
+                    material_bind_group_id: AtomicMaterialBindGroupId::default(),
+                    flags: RenderMeshInstanceFlags::empty(),
+                    render_layers: None,
+                    aabb: Some(Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5))),
+                },
+                transforms: MeshTransforms {
+                    world_from_local: Affine3::from(&Affine3A::from_scale_rotation_translation(
+                        Vec3::new(1.0, 3.0, 1.0),
+                        Quat::IDENTITY,
+                        Vec3::new(0.0, 0.0, -20.0),
+                    )),
+                    previous_world_from_local: Affine3::from(&Affine3A::IDENTITY),
+                    flags: 0,
+                },
+            },
+        );
+        let world_bounds = RenderMeshInstances::CpuBuilding(instances)
+            .world_bounds(entity, &[])
+            .unwrap();
+        assert_eq!(world_bounds.max_scale, 3.0);
+
+        let view = ExtractedView {
+            clip_from_view: Mat4::perspective_infinite_reverse_rh(
+                std::f32::consts::FRAC_PI_2,
+                1.0,
+                0.1,
+            ),
+            world_from_view: GlobalTransform::IDENTITY,
+            clip_from_world: None,
+            hdr: false,
+            viewport: UVec4::new(0, 0, 100, 100),
+            color_grading: ColorGrading::default(),
+        };
+        let clip_from_world = view.clip_from_view;
+        let bounds = transmissive_screen_space_bounds(
+            &view,
+            &clip_from_world,
+            &view.rangefinder3d(),
+            world_bounds,
+            SpecularTransmissionReach {
+                thickness: 1.0,
+                perceptual_roughness: 0.0,
+            },
+        )
+        .unwrap();
+
+        // Refraction can offset the background by up to the thickness scaled by the largest axis
+        // scale, not just by the unscaled thickness
+        let reach_bounds = |thickness: f32| {
+            screen_space_bounds(
+                &clip_from_world,
+                &Sphere {
+                    center: world_bounds.sphere.center,
+                    radius: world_bounds.sphere.radius + thickness,
+                },
+            )
+            .unwrap()
+        };
+        assert_eq!(bounds, reach_bounds(3.0));
+        assert!(reach_bounds(1.0).width() < bounds.width());
+    }
+}
@@ -243,9 +243,28 @@ pub struct StandardMaterial {
     ///     for a much less expensive effect.
     /// - Specular transmission is rendered before alpha blending, so any material with [`AlphaMode::Blend`], [`AlphaMode::Premultiplied`], [`AlphaMode::Add`] or [`AlphaMode::Multiply`]
     ///     won't be visible through specular transmissive materials.
+    ///
+    /// ## Deferred Rendering
+    ///
+    /// Specular transmissive materials are always rendered by the forward [`Transmissive3d`](bevy_core_pipeline::core_3d::Transmissive3d)
+    /// pass, regardless of [`StandardMaterial::opaque_render_method`]. On cameras using deferred rendering, that pass runs after the
+    /// deferred lighting pass, so deferred geometry is visible through (and refracted by) transmissive materials.
     #[doc(alias = "refraction")]
     pub specular_transmission: f32,
 
+    /// Overrides [`Camera3d::screen_space_specular_transmission_steps`](bevy_core_pipeline::core_3d::Camera3d::screen_space_specular_transmission_steps)
+    /// for this material.
+    ///
+    /// Consecutive (in depth order) transmissive materials using the same number of steps are rendered together. Transmissive
+    /// meshes behind and overlapping (in screen space) a mesh using this material use at least this many steps as well, so a
+    /// single “hero” object can see through more layers of transmissive objects than the rest of the scene. Setting this to
+    /// `Some(0)` lets the material reuse the previous step's transmission texture copy, avoiding an extra texture copy.
+    ///
+    /// Has no effect if the camera's number of steps is `0`, or if [`StandardMaterial::specular_transmission`] is `0.0`.
+    ///
+    /// Defaults to [`None`].
+    pub specular_transmission_steps: Option<usize>,
+
     /// The UV channel to use for the [`StandardMaterial::specular_transmission_texture`].
     ///
     /// Defaults to [`UvChannel::Uv0`].
@@ -581,6 +600,19 @@ pub struct StandardMaterial {
     /// Whether to enable fog for this material.
     pub fog_enabled: bool,
 
+    /// How strongly fog is applied to this material, from `0.0` (not at all) to `1.0` (fully).
+    ///
+    /// Useful for partially exempting emissive signage or skybox-like geometry from fog,
+    /// without splitting it into separate fogged and unfogged materials.
+    ///
+    /// Has no effect if [`StandardMaterial::fog_enabled`] is `false`.
+    ///
+    /// **Note:** Only supported by the forward rendering path. In the deferred path, fog is
+    /// applied fully whenever [`StandardMaterial::fog_enabled`] is `true`.
+    ///
+    /// Defaults to `1.0`.
+    pub fog_influence: f32,
+
     /// How to apply the alpha channel of the `base_color_texture`.
     ///
     /// See [`AlphaMode`] for details. Defaults to [`AlphaMode::Opaque`].
@@ -785,6 +817,7 @@ impl Default for StandardMaterial {
             #[cfg(feature = "pbr_transmission_textures")]
             diffuse_transmission_texture: None,
             specular_transmission: 0.0,
+            specular_transmission_steps: None,
             #[cfg(feature = "pbr_transmission_textures")]
             specular_transmission_channel: UvChannel::Uv0,
             #[cfg(feature = "pbr_transmission_textures")]
@@ -826,6 +859,7 @@ impl Default for StandardMaterial {
             cull_mode: Some(Face::Back),
             unlit: false,
             fog_enabled: true,
+            fog_influence: 1.0,
             alpha_mode: AlphaMode::Opaque,
             depth_bias: 0.0,
             depth_map: None,
@@ -959,6 +993,8 @@ pub struct StandardMaterialUniform {
     pub max_relief_mapping_search_steps: u32,
     /// ID for specifying which deferred lighting pass should be used for rendering this material, if any.
     pub deferred_lighting_pass_id: u32,
+    /// How strongly fog is applied to this material, from [0.0, 1.0]
+    pub fog_influence: f32,
 }
 
 impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
@@ -1095,6 +1131,7 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             lightmap_exposure: self.lightmap_exposure,
             max_relief_mapping_search_steps: self.parallax_mapping_method.max_steps(),
             deferred_lighting_pass_id: self.deferred_lighting_pass_id as u32,
+            fog_influence: self.fog_influence.clamp(0.0, 1.0),
             uv_transform: self.uv_transform.into(),
         }
     }
@@ -1281,6 +1318,20 @@ impl Material for StandardMaterial {
         self.specular_transmission > 0.0
     }
 
+    #[inline]
+    fn specular_transmission_steps(&self) -> Option<usize> {
+        self.specular_transmission_steps
+    }
+
+    #[inline]
+    fn specular_transmission_reach(&self) -> Option<SpecularTransmissionReach> {
+        // The thickness and roughness textures (if any) only scale these down
+        Some(SpecularTransmissionReach {
+            thickness: self.thickness,
+            perceptual_roughness: self.perceptual_roughness,
+        })
+    }
+
     fn prepass_fragment_shader() -> ShaderRef {
         PBR_PREPASS_SHADER_HANDLE.into()
     }
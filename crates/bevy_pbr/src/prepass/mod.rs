@@ -466,6 +466,13 @@ where
         );
         bind_group_layouts.insert(1, bind_group);
 
+        setup_custom_vertex_attributes(
+            &self.mesh_layouts,
+            layout,
+            &mut shader_defs,
+            &mut vertex_attributes,
+        );
+
         let vertex_buffer_layout = layout.0.get_layout(&vertex_attributes)?;
 
         // Setup prepass fragment targets - normals in slot 0 (or None if not needed), motion vectors in slot 1
@@ -1,14 +1,16 @@
 #define_import_path bevy_pbr::fog
 
-#import bevy_pbr::{
-    mesh_view_bindings::fog,
-    mesh_view_types::Fog,
+#import bevy_core_pipeline::fog::{
+    Fog,
+    aerial_perspective_lut_sample,
+    linear_fog_intensity,
+    exponential_fog_intensity,
+    exponential_squared_fog_intensity,
 }
+#import bevy_pbr::mesh_view_bindings::fog
 
-// Fog formulas adapted from:
-// https://learn.microsoft.com/en-us/windows/win32/direct3d9/fog-formulas
-// https://catlikecoding.com/unity/tutorials/rendering/part-14/
-// https://iquilezles.org/articles/fog/ (Atmospheric Fog and Scattering)
+// The fog struct, falloff formulas and mode constants are shared with the 2d pipelines, and
+// defined in `bevy_core_pipeline::fog`. Only directional light scattering is specific to 3d.
 
 fn scattering_adjusted_fog_color(
     fog_params: Fog,
@@ -25,6 +27,28 @@ fn scattering_adjusted_fog_color(
     }
 }
 
+// The inscattered color of the aerial perspective mode, for a fragment. The lookup table is read
+// from the view binding, see `aerial_perspective_lut_sample()`
+fn aerial_perspective_fog_color(
+    fog_params: Fog,
+    view_world_position: vec3<f32>,
+    fragment_world_position: vec3<f32>,
+) -> vec4<f32> {
+    let lut_sample = aerial_perspective_lut_sample(fog_params, view_world_position, fragment_world_position);
+    return vec4<f32>(
+        mix(
+            fog.aerial_perspective_lut[lut_sample.index].rgb,
+            fog.aerial_perspective_lut[lut_sample.index + 1u].rgb,
+            lut_sample.blend,
+        ),
+        fog_params.base_color.a,
+    );
+}
+
+// The functions below apply a single falloff mode, and are kept for existing shaders. The
+// built-in shaders use `bevy_core_pipeline::fog::apply_fog_color()` instead, which handles all
+// modes, the fog start distance and density noise.
+
 fn linear_fog(
     fog_params: Fog,
     input_color: vec4<f32>,
@@ -32,9 +56,7 @@ fn linear_fog(
     scattering: vec3<f32>,
 ) -> vec4<f32> {
     var fog_color = scattering_adjusted_fog_color(fog_params, scattering);
-    let start = fog_params.be.x;
-    let end = fog_params.be.y;
-    fog_color.a *= 1.0 - clamp((end - distance) / (end - start), 0.0, 1.0);
+    fog_color.a *= min(linear_fog_intensity(fog_params, distance), fog_params.max_opacity);
     return vec4<f32>(mix(input_color.rgb, fog_color.rgb, fog_color.a), input_color.a);
 }
 
@@ -45,8 +67,7 @@ fn exponential_fog(
     scattering: vec3<f32>,
 ) -> vec4<f32> {
     var fog_color = scattering_adjusted_fog_color(fog_params, scattering);
-    let density = fog_params.be.x;
-    fog_color.a *= 1.0 - 1.0 / exp(distance * density);
+    fog_color.a *= min(exponential_fog_intensity(fog_params, distance), fog_params.max_opacity);
     return vec4<f32>(mix(input_color.rgb, fog_color.rgb, fog_color.a), input_color.a);
 }
 
@@ -57,8 +78,7 @@ fn exponential_squared_fog(
     scattering: vec3<f32>,
 ) -> vec4<f32> {
     var fog_color = scattering_adjusted_fog_color(fog_params, scattering);
-    let distance_times_density = distance * fog_params.be.x;
-    fog_color.a *= 1.0 - 1.0 / exp(distance_times_density * distance_times_density);
+    fog_color.a *= min(exponential_squared_fog_intensity(fog_params, distance), fog_params.max_opacity);
     return vec4<f32>(mix(input_color.rgb, fog_color.rgb, fog_color.a), input_color.a);
 }
 
@@ -68,12 +88,10 @@ fn atmospheric_fog(
     distance: f32,
     scattering: vec3<f32>,
 ) -> vec4<f32> {
-    var fog_color = scattering_adjusted_fog_color(fog_params, scattering);
-    let extinction_factor = 1.0 - 1.0 / exp(distance * fog_params.be);
-    let inscattering_factor = 1.0 - 1.0 / exp(distance * fog_params.bi);
-    return vec4<f32>(
-        input_color.rgb * (1.0 - extinction_factor * fog_color.a)
-            + fog_color.rgb * inscattering_factor * fog_color.a,
-        input_color.a
+    return bevy_core_pipeline::fog::atmospheric_fog(
+        fog_params,
+        input_color,
+        distance,
+        scattering_adjusted_fog_color(fog_params, scattering),
     );
 }
@@ -14,7 +14,7 @@ use bevy_ecs::{
     query::ROQueryItem,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
-use bevy_math::{Affine3, Rect, UVec2, Vec3, Vec4};
+use bevy_math::{Affine3, Mat3, Rect, UVec2, Vec3, Vec4, Vec4Swizzles};
 use bevy_render::{
     batching::{
         gpu_preprocessing::{
@@ -24,7 +24,7 @@ use bevy_render::{
     },
     camera::Camera,
     mesh::*,
-    primitives::Aabb,
+    primitives::{Aabb, Sphere},
     render_asset::RenderAssets,
     render_phase::{
         BinnedRenderPhasePlugin, PhaseItem, RenderCommand, RenderCommandResult,
@@ -66,6 +66,143 @@ pub struct MeshRenderPlugin {
     pub use_gpu_instance_buffer_builder: bool,
 }
 
+/// The first shader location available to [`CustomVertexAttribute`]s, as the lower
+/// ones are used by the standard vertex attributes of the [`MeshPipeline`] and
+/// [`PrepassPipeline`].
+pub const FIRST_CUSTOM_VERTEX_ATTRIBUTE_LOCATION: u32 = 8;
+
+/// A vertex attribute, beyond the standard ones, that the [`MeshPipeline`] and
+/// [`PrepassPipeline`] pass to vertex shaders when a mesh has it.
+///
+/// This lets meshes carry extra per-vertex data (e.g. wind weights for vegetation)
+/// to custom vertex shaders, such as those of a [`MaterialExtension`], without a
+/// fully custom pipeline. Register it with a [`CustomVertexAttributePlugin`].
+#[derive(Clone, Copy, Debug)]
+pub struct CustomVertexAttribute {
+    /// The attribute, as inserted into the [`Mesh`].
+    pub attribute: MeshVertexAttribute,
+    /// The shader location of the attribute in the vertex shader input.
+    ///
+    /// Must be at least [`FIRST_CUSTOM_VERTEX_ATTRIBUTE_LOCATION`].
+    pub shader_location: u32,
+    /// A shader def that's set when the mesh has the attribute.
+    pub shader_def: &'static str,
+}
+
+/// The [`CustomVertexAttribute`]s registered with [`CustomVertexAttributePlugin`]s.
+#[derive(Resource, Clone, Default, Deref, DerefMut)]
+pub struct CustomVertexAttributes(pub Vec<CustomVertexAttribute>);
+
+/// Registers a [`CustomVertexAttribute`] with the [`MeshPipeline`] and [`PrepassPipeline`].
+///
+/// Can be added multiple times, once per attribute. The attribute, shader location and shader
+/// def of each registered attribute must be unique.
+///
+/// # Example
+///
+/// Passing per-vertex wind weights to the vertex shader of a [`MaterialExtension`], to make
+/// vegetation sway:
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_asset::Asset;
+/// # use bevy_pbr::{
+/// #     CustomVertexAttribute, CustomVertexAttributePlugin, ExtendedMaterial, MaterialExtension,
+/// #     MaterialPlugin, StandardMaterial,
+/// # };
+/// # use bevy_reflect::Reflect;
+/// # use bevy_render::{
+/// #     mesh::MeshVertexAttribute,
+/// #     render_resource::{AsBindGroup, ShaderRef, VertexFormat},
+/// # };
+/// const ATTRIBUTE_WIND_WEIGHT: MeshVertexAttribute =
+///     MeshVertexAttribute::new("WindWeight", 988540917, VertexFormat::Float32);
+///
+/// #[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+/// struct WindExtension {}
+///
+/// impl MaterialExtension for WindExtension {
+///     fn vertex_shader() -> ShaderRef {
+///         "shaders/wind.wgsl".into()
+///     }
+///
+///     // Also sway in the prepass, so that shadows match
+///     fn prepass_vertex_shader() -> ShaderRef {
+///         "shaders/wind.wgsl".into()
+///     }
+/// }
+///
+/// fn build(app: &mut App) {
+///     app.add_plugins((
+///         CustomVertexAttributePlugin(CustomVertexAttribute {
+///             attribute: ATTRIBUTE_WIND_WEIGHT,
+///             shader_location: 8,
+///             shader_def: "VERTEX_WIND_WEIGHTS",
+///         }),
+///         MaterialPlugin::<ExtendedMaterial<StandardMaterial, WindExtension>>::default(),
+///     ));
+/// }
+/// ```
+/// Meshes with an `ATTRIBUTE_WIND_WEIGHT` then get the shader def set, and the attribute at the
+/// given location:
+///
+/// ```wgsl
+/// struct Vertex {
+///     @builtin(instance_index) instance_index: u32,
+///     @location(0) position: vec3<f32>,
+///     // ...
+/// #ifdef VERTEX_WIND_WEIGHTS
+///     @location(8) wind_weight: f32,
+/// #endif
+/// };
+/// ```
+pub struct CustomVertexAttributePlugin(pub CustomVertexAttribute);
+
+impl Plugin for CustomVertexAttributePlugin {
+    fn build(&self, app: &mut App) {
+        assert!(
+            self.0.shader_location >= FIRST_CUSTOM_VERTEX_ATTRIBUTE_LOCATION,
+            "the shader location of custom vertex attribute `{}` must be at least {FIRST_CUSTOM_VERTEX_ATTRIBUTE_LOCATION}",
+            self.0.attribute.name,
+        );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        let mut custom_vertex_attributes = render_app
+            .world_mut()
+            .get_resource_or_insert_with(CustomVertexAttributes::default);
+
+        for registered in custom_vertex_attributes.iter() {
+            assert!(
+                registered.attribute.id != self.0.attribute.id,
+                "custom vertex attribute `{}` is already registered",
+                self.0.attribute.name,
+            );
+            assert!(
+                registered.shader_location != self.0.shader_location,
+                "custom vertex attributes `{}` and `{}` both use shader location {}",
+                registered.attribute.name,
+                self.0.attribute.name,
+                self.0.shader_location,
+            );
+            assert!(
+                registered.shader_def != self.0.shader_def,
+                "custom vertex attributes `{}` and `{}` both use shader def `{}`",
+                registered.attribute.name,
+                self.0.attribute.name,
+                self.0.shader_def,
+            );
+        }
+
+        custom_vertex_attributes.push(self.0);
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
 pub const FORWARD_IO_HANDLE: Handle<Shader> = Handle::weak_from_u128(2645551199423808407);
 pub const MESH_VIEW_TYPES_HANDLE: Handle<Shader> = Handle::weak_from_u128(8140454348013264787);
 pub const MESH_VIEW_BINDINGS_HANDLE: Handle<Shader> = Handle::weak_from_u128(9076678235888822571);
@@ -120,7 +257,6 @@ impl Plugin for MeshRenderPlugin {
             Shader::from_wgsl
         );
         load_internal_asset!(app, MESH_SHADER_HANDLE, "mesh.wgsl", Shader::from_wgsl);
-        load_internal_asset!(app, SKINNING_HANDLE, "skinning.wgsl", Shader::from_wgsl);
         load_internal_asset!(app, MORPH_HANDLE, "morph.wgsl", Shader::from_wgsl);
 
         app.add_systems(
@@ -140,7 +276,6 @@ impl Plugin for MeshRenderPlugin {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<MeshBindGroups>()
-                .init_resource::<SkinUniforms>()
                 .init_resource::<SkinIndices>()
                 .init_resource::<MorphUniforms>()
                 .init_resource::<MorphIndices>()
@@ -172,6 +307,7 @@ impl Plugin for MeshRenderPlugin {
 
     fn finish(&self, app: &mut App) {
         let mut mesh_bindings_shader_defs = Vec::with_capacity(1);
+        let mut skinning_shader_defs = Vec::with_capacity(1);
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<GpuPreprocessingSupport>();
@@ -232,7 +368,12 @@ impl Plugin for MeshRenderPlugin {
                 ));
             }
 
+            if skins_use_uniform_buffers(render_device) {
+                skinning_shader_defs.push("SKINS_USE_UNIFORM_BUFFERS".into());
+            }
+
             render_app
+                .init_resource::<SkinUniforms>()
                 .init_resource::<MeshPipelineViewLayouts>()
                 .init_resource::<MeshPipeline>();
         }
@@ -246,6 +387,14 @@ impl Plugin for MeshRenderPlugin {
             Shader::from_wgsl_with_defs,
             mesh_bindings_shader_defs
         );
+        // Likewise for the skinning shader module, which depends on whether storage buffers are supported.
+        load_internal_asset!(
+            app,
+            SKINNING_HANDLE,
+            "skinning.wgsl",
+            Shader::from_wgsl_with_defs,
+            skinning_shader_defs
+        );
     }
 }
 
@@ -502,6 +651,11 @@ pub struct RenderMeshInstanceShared {
     pub flags: RenderMeshInstanceFlags,
     /// Render layers
     pub render_layers: Option<RenderLayers>,
+    /// The local space bounding box of the mesh instance, if it has one.
+    ///
+    /// Used to estimate the screen space coverage of transmissive meshes. See
+    /// [`RenderMeshInstances::world_bounds`].
+    pub aabb: Option<Aabb>,
 }
 
 /// Information that is gathered during the parallel portion of mesh extraction
@@ -565,6 +719,7 @@ pub struct RenderMeshInstanceGpuQueues(Parallel<RenderMeshInstanceGpuQueue>);
 
 impl RenderMeshInstanceShared {
     fn from_components(
+        aabb: Option<&Aabb>,
         previous_transform: Option<&PreviousGlobalTransform>,
         handle: &Handle<Mesh>,
         not_shadow_caster: bool,
@@ -587,6 +742,7 @@ impl RenderMeshInstanceShared {
             render_layers,
             flags: mesh_instance_flags,
             material_bind_group_id: AtomicMaterialBindGroupId::default(),
+            aabb: aabb.copied(),
         }
     }
 
@@ -654,6 +810,50 @@ impl RenderMeshInstances {
         }
     }
 
+    /// Computes the world space bounds of the given entity, if it has a mesh
+    /// with a bounding box attached.
+    ///
+    /// This isn't done during extraction, since only a few meshes (e.g.
+    /// transmissive ones) need it. When using GPU mesh instance data building,
+    /// the transforms are read from `mesh_input_uniforms`, which must be the
+    /// current input buffer.
+    pub fn world_bounds(
+        &self,
+        entity: Entity,
+        mesh_input_uniforms: &[MeshInputUniform],
+    ) -> Option<MeshWorldBounds> {
+        let (aabb, world_from_local) = match *self {
+            RenderMeshInstances::CpuBuilding(ref instances) => {
+                let instance = instances.get(&entity)?;
+                (instance.aabb?, instance.transforms.world_from_local)
+            }
+            RenderMeshInstances::GpuBuilding(ref instances) => {
+                let instance = instances.get(&entity)?;
+                let [x_row, y_row, z_row] = mesh_input_uniforms
+                    .get(instance.current_uniform_index.get() as usize)?
+                    .world_from_local;
+                let world_from_local = Affine3 {
+                    matrix3: Mat3::from_cols(x_row.xyz(), y_row.xyz(), z_row.xyz()).transpose(),
+                    translation: Vec3::new(x_row.w, y_row.w, z_row.w),
+                };
+                (instance.aabb?, world_from_local)
+            }
+        };
+
+        let matrix3 = world_from_local.matrix3;
+        Some(MeshWorldBounds {
+            sphere: Sphere {
+                center: (matrix3 * Vec3::from(aabb.center) + world_from_local.translation).into(),
+                radius: (matrix3 * Vec3::from(aabb.half_extents)).length(),
+            },
+            max_scale: matrix3
+                .x_axis
+                .length()
+                .max(matrix3.y_axis.length())
+                .max(matrix3.z_axis.length()),
+        })
+    }
+
     /// Inserts the given flags into the CPU or GPU render mesh instance data
     /// for the given mesh as appropriate.
     fn insert_mesh_instance_flags(&mut self, entity: Entity, flags: RenderMeshInstanceFlags) {
@@ -668,6 +868,17 @@ impl RenderMeshInstances {
     }
 }
 
+/// The world space bounds of a mesh instance, see
+/// [`RenderMeshInstances::world_bounds`].
+#[derive(Clone, Copy, Debug)]
+pub struct MeshWorldBounds {
+    /// A sphere enclosing the bounding box of the mesh.
+    pub sphere: Sphere,
+    /// The largest scale the transform of the mesh applies along any of its
+    /// local axes.
+    pub max_scale: f32,
+}
+
 impl RenderMeshInstancesCpu {
     fn mesh_asset_id(&self, entity: Entity) -> Option<AssetId<Mesh>> {
         self.get(&entity)
@@ -875,6 +1086,7 @@ pub fn extract_meshes_for_cpu_building(
             &ViewVisibility,
             &GlobalTransform,
             Option<&PreviousGlobalTransform>,
+            Option<&Aabb>,
             Option<&RenderLayers>,
             &Handle<Mesh>,
             Has<NotShadowReceiver>,
@@ -893,6 +1105,7 @@ pub fn extract_meshes_for_cpu_building(
             view_visibility,
             transform,
             previous_transform,
+            aabb,
             render_layers,
             handle,
             not_shadow_receiver,
@@ -918,6 +1131,7 @@ pub fn extract_meshes_for_cpu_building(
             );
 
             let shared = RenderMeshInstanceShared::from_components(
+                aabb,
                 previous_transform,
                 handle,
                 not_shadow_caster,
@@ -1037,6 +1251,7 @@ pub fn extract_meshes_for_gpu_building(
             );
 
             let shared = RenderMeshInstanceShared::from_components(
+                aabb,
                 previous_transform,
                 handle,
                 not_shadow_caster,
@@ -1195,6 +1410,10 @@ pub struct MeshPipeline {
 
 impl FromWorld for MeshPipeline {
     fn from_world(world: &mut World) -> Self {
+        let custom_vertex_attributes = world
+            .get_resource::<CustomVertexAttributes>()
+            .cloned()
+            .unwrap_or_default();
         let mut system_state: SystemState<(
             Res<RenderDevice>,
             Res<DefaultImageSampler>,
@@ -1245,7 +1464,10 @@ impl FromWorld for MeshPipeline {
             view_layouts: view_layouts.clone(),
             clustered_forward_buffer_binding_type,
             dummy_white_gpu_image,
-            mesh_layouts: MeshLayouts::new(&render_device),
+            mesh_layouts: MeshLayouts {
+                custom_vertex_attributes: custom_vertex_attributes.0,
+                ..MeshLayouts::new(&render_device)
+            },
             per_object_buffer_batch_size: GpuArrayBuffer::<MeshUniform>::batch_size(&render_device),
             binding_arrays_are_usable: binding_arrays_are_usable(&render_device),
         }
@@ -1500,7 +1722,9 @@ bitflags::bitflags! {
         const SCREEN_SPACE_REFLECTIONS          = 1 << 16;
         const HAS_PREVIOUS_SKIN                 = 1 << 17;
         const HAS_PREVIOUS_MORPH                = 1 << 18;
-        const LAST_FLAG                         = Self::HAS_PREVIOUS_MORPH.bits();
+        const SCREEN_SPACE_SPECULAR_TRANSMISSION_MIP_CHAIN = 1 << 19;
+        const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM = 1 << 20; // ← Uses the `SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_*` bitfields
+        const LAST_FLAG                         = Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM.bits();
 
         // Bitfields
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
@@ -1533,13 +1757,19 @@ bitflags::bitflags! {
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_MEDIUM = 1 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_HIGH = 2 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_ULTRA = 3 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+        const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_RESERVED_BITS = Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_MASK_BITS << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_SHIFT_BITS;
+        const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_RESERVED_BITS = Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_MASK_BITS << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_SHIFT_BITS;
+        const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_RESERVED_BITS = Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_MASK_BITS << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_SHIFT_BITS;
         const ALL_RESERVED_BITS =
             Self::BLEND_RESERVED_BITS.bits() |
             Self::MSAA_RESERVED_BITS.bits() |
             Self::TONEMAP_METHOD_RESERVED_BITS.bits() |
             Self::SHADOW_FILTER_METHOD_RESERVED_BITS.bits() |
             Self::VIEW_PROJECTION_RESERVED_BITS.bits() |
-            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS.bits();
+            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS.bits() |
+            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_RESERVED_BITS.bits() |
+            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_RESERVED_BITS.bits() |
+            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_RESERVED_BITS.bits();
     }
 }
 
@@ -1567,6 +1797,24 @@ impl MeshPipelineKey {
     const SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS: u64 =
         Self::VIEW_PROJECTION_MASK_BITS.count_ones() as u64 + Self::VIEW_PROJECTION_SHIFT_BITS;
 
+    // Stores `taps - 1`, so `1..=64` taps
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_MASK_BITS: u64 = 0b111111;
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_SHIFT_BITS: u64 =
+        Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS.count_ones() as u64
+            + Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+
+    // Stores `spiral_turns - 1`, so `1..=8` turns
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_MASK_BITS: u64 = 0b111;
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_SHIFT_BITS: u64 =
+        Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_MASK_BITS.count_ones() as u64
+            + Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_SHIFT_BITS;
+
+    // Stores `fractional_jitter` in `1/15` steps
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_MASK_BITS: u64 = 0b1111;
+    const SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_SHIFT_BITS: u64 =
+        Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_MASK_BITS.count_ones() as u64
+            + Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_SHIFT_BITS;
+
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
         let msaa_bits =
             (msaa_samples.trailing_zeros() as u64 & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
@@ -1581,6 +1829,55 @@ impl MeshPipelineKey {
         }
     }
 
+    /// Encodes the parameters of [`ScreenSpaceTransmissionQuality::Custom`](bevy_core_pipeline::core_3d::ScreenSpaceTransmissionQuality::Custom).
+    pub fn from_screen_space_specular_transmission_custom(
+        taps: u32,
+        spiral_turns: u32,
+        fractional_jitter: f32,
+    ) -> Self {
+        let taps_bits = (taps.clamp(1, 64) as u64 - 1)
+            & Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_MASK_BITS;
+        let spiral_turns_bits = (spiral_turns.clamp(1, 8) as u64 - 1)
+            & Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_MASK_BITS;
+        let jitter_bits = ((fractional_jitter.clamp(0.0, 1.0) * 15.0).round() as u64)
+            & Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_MASK_BITS;
+        Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM
+            | Self::from_bits_retain(
+                (taps_bits << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_SHIFT_BITS)
+                    | (spiral_turns_bits
+                        << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_SHIFT_BITS)
+                    | (jitter_bits
+                        << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_SHIFT_BITS),
+            )
+    }
+
+    /// Decodes the parameters encoded by
+    /// [`MeshPipelineKey::from_screen_space_specular_transmission_custom`], as
+    /// `(taps, spiral_turns, jitter_fifteenths)`, if
+    /// [`MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM`] is set.
+    pub fn screen_space_specular_transmission_custom(&self) -> Option<(u32, u32, u32)> {
+        if !self.contains(Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM) {
+            return None;
+        }
+
+        let custom_bits =
+            |shift_bits: u64, mask_bits: u64| ((self.bits() >> shift_bits) & mask_bits) as u32;
+        Some((
+            custom_bits(
+                Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_SHIFT_BITS,
+                Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_TAPS_MASK_BITS,
+            ) + 1,
+            custom_bits(
+                Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_SHIFT_BITS,
+                Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_SPIRAL_TURNS_MASK_BITS,
+            ) + 1,
+            custom_bits(
+                Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_SHIFT_BITS,
+                Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM_JITTER_MASK_BITS,
+            ),
+        ))
+    }
+
     pub fn msaa_samples(&self) -> u32 {
         1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
     }
@@ -1679,6 +1976,22 @@ pub fn setup_morph_and_skinning_defs(
     }
 }
 
+/// Adds the [`CustomVertexAttribute`]s of `mesh_layouts` that the mesh has to the
+/// vertex attributes and shader defs.
+pub fn setup_custom_vertex_attributes(
+    mesh_layouts: &MeshLayouts,
+    layout: &MeshVertexBufferLayoutRef,
+    shader_defs: &mut Vec<ShaderDefVal>,
+    vertex_attributes: &mut Vec<VertexAttributeDescriptor>,
+) {
+    for custom in &mesh_layouts.custom_vertex_attributes {
+        if layout.0.contains(custom.attribute) {
+            shader_defs.push(custom.shader_def.into());
+            vertex_attributes.push(custom.attribute.at_shader_location(custom.shader_location));
+        }
+    }
+}
+
 impl SpecializedMeshPipeline for MeshPipeline {
     type Key = MeshPipelineKey;
 
@@ -1752,6 +2065,13 @@ impl SpecializedMeshPipeline for MeshPipeline {
             &mut vertex_attributes,
         ));
 
+        setup_custom_vertex_attributes(
+            &self.mesh_layouts,
+            layout,
+            &mut shader_defs,
+            &mut vertex_attributes,
+        );
+
         if key.contains(MeshPipelineKey::SCREEN_SPACE_AMBIENT_OCCLUSION) {
             shader_defs.push("SCREEN_SPACE_AMBIENT_OCCLUSION".into());
         }
@@ -1922,19 +2242,40 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("SHADOW_FILTER_METHOD_TEMPORAL".into());
         }
 
-        let blur_quality =
-            key.intersection(MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS);
+        if let Some((taps, spiral_turns, jitter_fifteenths)) =
+            key.screen_space_specular_transmission_custom()
+        {
+            shader_defs.push(ShaderDefVal::Int(
+                "SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_TAPS".into(),
+                taps as i32,
+            ));
+            shader_defs.push(ShaderDefVal::UInt(
+                "SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_SPIRAL_TURNS".into(),
+                spiral_turns,
+            ));
+            shader_defs.push(ShaderDefVal::UInt(
+                "SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_JITTER_FIFTEENTHS".into(),
+                jitter_fifteenths,
+            ));
+        } else {
+            let blur_quality =
+                key.intersection(MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS);
+
+            shader_defs.push(ShaderDefVal::Int(
+                "SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_TAPS".into(),
+                match blur_quality {
+                    MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_LOW => 4,
+                    MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_MEDIUM => 8,
+                    MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_HIGH => 16,
+                    MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_ULTRA => 32,
+                    _ => unreachable!(), // Not possible, since the mask is 2 bits, and we've covered all 4 cases
+                },
+            ));
+        }
 
-        shader_defs.push(ShaderDefVal::Int(
-            "SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_TAPS".into(),
-            match blur_quality {
-                MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_LOW => 4,
-                MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_MEDIUM => 8,
-                MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_HIGH => 16,
-                MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_ULTRA => 32,
-                _ => unreachable!(), // Not possible, since the mask is 2 bits, and we've covered all 4 cases
-            },
-        ));
+        if key.contains(MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_MIP_CHAIN) {
+            shader_defs.push("SCREEN_SPACE_SPECULAR_TRANSMISSION_MIP_CHAIN".into());
+        }
 
         if key.contains(MeshPipelineKey::VISIBILITY_RANGE_DITHER) {
             shader_defs.push("VISIBILITY_RANGE_DITHER".into());
@@ -2113,9 +2454,9 @@ pub fn prepare_mesh_bind_group(
     // Create the skinned mesh bind group with the current and previous buffers
     // (the latter being for motion vector computation). If there's no previous
     // buffer, just use the current one as the shader will ignore it.
-    let skin = skins_uniform.current_buffer.buffer();
-    if let Some(skin) = skin {
-        let prev_skin = skins_uniform.prev_buffer.buffer().unwrap_or(skin);
+    let skin = skins_uniform.current_binding();
+    let prev_skin = skins_uniform.prev_binding().or_else(|| skin.clone());
+    if let (Some(skin), Some(prev_skin)) = (&skin, &prev_skin) {
         groups.skinned = Some(MeshBindGroupPair {
             motion_vectors: layouts.skinned_motion(&render_device, &model, skin, prev_skin),
             no_motion_vectors: layouts.skinned(&render_device, &model, skin),
@@ -2128,9 +2469,8 @@ pub fn prepare_mesh_bind_group(
         let prev_weights = weights_uniform.prev_buffer.buffer().unwrap_or(weights);
         for (id, gpu_mesh) in meshes.iter() {
             if let Some(targets) = gpu_mesh.morph_targets.as_ref() {
-                let bind_group_pair = match skin.filter(|_| is_skinned(&gpu_mesh.layout)) {
-                    Some(skin) => {
-                        let prev_skin = skins_uniform.prev_buffer.buffer().unwrap_or(skin);
+                let bind_group_pair = match (&skin, &prev_skin) {
+                    (Some(skin), Some(prev_skin)) if is_skinned(&gpu_mesh.layout) => {
                         MeshBindGroupPair {
                             motion_vectors: layouts.morphed_skinned_motion(
                                 &render_device,
@@ -2150,7 +2490,7 @@ pub fn prepare_mesh_bind_group(
                             ),
                         }
                     }
-                    None => MeshBindGroupPair {
+                    _ => MeshBindGroupPair {
                         motion_vectors: layouts.morphed_motion(
                             &render_device,
                             &model,
@@ -2458,4 +2798,37 @@ mod tests {
             assert_eq!(MeshPipelineKey::from_msaa_samples(i).msaa_samples(), i);
         }
     }
+
+    #[test]
+    fn mesh_key_screen_space_specular_transmission_custom() {
+        assert_eq!(
+            MeshPipelineKey::NONE.screen_space_specular_transmission_custom(),
+            None
+        );
+
+        for (taps, spiral_turns, fractional_jitter, expected) in [
+            (1, 1, 0.0, (1, 1, 0)),
+            (64, 8, 1.0, (64, 8, 15)),
+            (12, 3, 0.5, (12, 3, 8)),
+            (0, 0, -1.0, (1, 1, 0)),
+            (100, 20, 2.0, (64, 8, 15)),
+        ] {
+            let key = MeshPipelineKey::from_screen_space_specular_transmission_custom(
+                taps,
+                spiral_turns,
+                fractional_jitter,
+            ) | MeshPipelineKey::from_msaa_samples(128)
+                | MeshPipelineKey::HDR;
+            assert!(key.contains(MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_CUSTOM));
+            assert_eq!(
+                key.screen_space_specular_transmission_custom(),
+                Some(expected)
+            );
+            // The bitfields must not overlap neighboring ones
+            assert_eq!(key.msaa_samples(), 128);
+            assert!(key
+                .intersection(MeshPipelineKey::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS)
+                .is_empty());
+        }
+    }
 }
@@ -5,7 +5,7 @@ use bevy_render::{
     mesh::morph::MAX_MORPH_WEIGHTS, render_resource::*, renderer::RenderDevice, texture::GpuImage,
 };
 
-use crate::render::skin::MAX_JOINTS;
+use crate::{render::skin::MAX_JOINTS, CustomVertexAttribute};
 
 const MORPH_WEIGHT_SIZE: usize = size_of::<f32>();
 pub const MORPH_BUFFER_SIZE: usize = MAX_MORPH_WEIGHTS * MORPH_WEIGHT_SIZE;
@@ -15,11 +15,14 @@ pub(crate) const JOINT_BUFFER_SIZE: usize = MAX_JOINTS * JOINT_SIZE;
 
 /// Individual layout entries.
 mod layout_entry {
-    use super::{JOINT_BUFFER_SIZE, MORPH_BUFFER_SIZE};
-    use crate::MeshUniform;
+    use super::{JOINT_BUFFER_SIZE, JOINT_SIZE, MORPH_BUFFER_SIZE};
+    use crate::{render::skin::skins_use_uniform_buffers, MeshUniform};
     use bevy_render::{
         render_resource::{
-            binding_types::{sampler, texture_2d, texture_3d, uniform_buffer_sized},
+            binding_types::{
+                sampler, storage_buffer_read_only_sized, texture_2d, texture_3d,
+                uniform_buffer_sized,
+            },
             BindGroupLayoutEntryBuilder, BufferSize, GpuArrayBuffer, SamplerBindingType,
             ShaderStages, TextureSampleType,
         },
@@ -30,8 +33,12 @@ mod layout_entry {
         GpuArrayBuffer::<MeshUniform>::binding_layout(render_device)
             .visibility(ShaderStages::VERTEX_FRAGMENT)
     }
-    pub(super) fn skinning() -> BindGroupLayoutEntryBuilder {
-        uniform_buffer_sized(true, BufferSize::new(JOINT_BUFFER_SIZE as u64))
+    pub(super) fn skinning(render_device: &RenderDevice) -> BindGroupLayoutEntryBuilder {
+        if skins_use_uniform_buffers(render_device) {
+            uniform_buffer_sized(true, BufferSize::new(JOINT_BUFFER_SIZE as u64))
+        } else {
+            storage_buffer_read_only_sized(true, BufferSize::new(JOINT_SIZE as u64))
+        }
     }
     pub(super) fn weights() -> BindGroupLayoutEntryBuilder {
         uniform_buffer_sized(true, BufferSize::new(MORPH_BUFFER_SIZE as u64))
@@ -50,7 +57,7 @@ mod layout_entry {
 /// Individual [`BindGroupEntry`]
 /// for bind groups.
 mod entry {
-    use super::{JOINT_BUFFER_SIZE, MORPH_BUFFER_SIZE};
+    use super::MORPH_BUFFER_SIZE;
     use bevy_render::render_resource::{
         BindGroupEntry, BindingResource, Buffer, BufferBinding, BufferSize, Sampler, TextureView,
     };
@@ -68,8 +75,8 @@ mod entry {
     pub(super) fn model(binding: u32, resource: BindingResource) -> BindGroupEntry {
         BindGroupEntry { binding, resource }
     }
-    pub(super) fn skinning(binding: u32, buffer: &Buffer) -> BindGroupEntry {
-        entry(binding, JOINT_BUFFER_SIZE as u64, buffer)
+    pub(super) fn skinning(binding: u32, resource: BindingResource) -> BindGroupEntry {
+        BindGroupEntry { binding, resource }
     }
     pub(super) fn weights(binding: u32, buffer: &Buffer) -> BindGroupEntry {
         entry(binding, MORPH_BUFFER_SIZE as u64, buffer)
@@ -129,6 +136,10 @@ pub struct MeshLayouts {
     /// previous frame's joint matrices and morph weights, so that we can
     /// compute motion vectors.
     pub morphed_skinned_motion: BindGroupLayout,
+
+    /// Vertex attributes, beyond the standard ones, that pipelines using these
+    /// layouts pass to vertex shaders. See [`CustomVertexAttribute`].
+    pub custom_vertex_attributes: Vec<CustomVertexAttribute>,
 }
 
 impl MeshLayouts {
@@ -145,6 +156,7 @@ impl MeshLayouts {
             morphed_motion: Self::morphed_motion_layout(render_device),
             morphed_skinned: Self::morphed_skinned_layout(render_device),
             morphed_skinned_motion: Self::morphed_skinned_motion_layout(render_device),
+            custom_vertex_attributes: Vec::new(),
         }
     }
 
@@ -169,7 +181,7 @@ impl MeshLayouts {
                 (
                     (0, layout_entry::model(render_device)),
                     // The current frame's joint matrix buffer.
-                    (1, layout_entry::skinning()),
+                    (1, layout_entry::skinning(render_device)),
                 ),
             ),
         )
@@ -185,9 +197,9 @@ impl MeshLayouts {
                 (
                     (0, layout_entry::model(render_device)),
                     // The current frame's joint matrix buffer.
-                    (1, layout_entry::skinning()),
+                    (1, layout_entry::skinning(render_device)),
                     // The previous frame's joint matrix buffer.
-                    (6, layout_entry::skinning()),
+                    (6, layout_entry::skinning(render_device)),
                 ),
             ),
         )
@@ -238,7 +250,7 @@ impl MeshLayouts {
                 (
                     (0, layout_entry::model(render_device)),
                     // The current frame's joint matrix buffer.
-                    (1, layout_entry::skinning()),
+                    (1, layout_entry::skinning(render_device)),
                     // The current frame's morph weight buffer.
                     (2, layout_entry::weights()),
                     (3, layout_entry::targets()),
@@ -257,12 +269,12 @@ impl MeshLayouts {
                 (
                     (0, layout_entry::model(render_device)),
                     // The current frame's joint matrix buffer.
-                    (1, layout_entry::skinning()),
+                    (1, layout_entry::skinning(render_device)),
                     // The current frame's morph weight buffer.
                     (2, layout_entry::weights()),
                     (3, layout_entry::targets()),
                     // The previous frame's joint matrix buffer.
-                    (6, layout_entry::skinning()),
+                    (6, layout_entry::skinning(render_device)),
                     // The previous frame's morph weight buffer.
                     (7, layout_entry::weights()),
                 ),
@@ -316,14 +328,14 @@ impl MeshLayouts {
         &self,
         render_device: &RenderDevice,
         model: &BindingResource,
-        current_skin: &Buffer,
+        current_skin: &BindingResource,
     ) -> BindGroup {
         render_device.create_bind_group(
             "skinned_mesh_bind_group",
             &self.skinned,
             &[
                 entry::model(0, model.clone()),
-                entry::skinning(1, current_skin),
+                entry::skinning(1, current_skin.clone()),
             ],
         )
     }
@@ -339,16 +351,16 @@ impl MeshLayouts {
         &self,
         render_device: &RenderDevice,
         model: &BindingResource,
-        current_skin: &Buffer,
-        prev_skin: &Buffer,
+        current_skin: &BindingResource,
+        prev_skin: &BindingResource,
     ) -> BindGroup {
         render_device.create_bind_group(
             "skinned_motion_mesh_bind_group",
             &self.skinned_motion,
             &[
                 entry::model(0, model.clone()),
-                entry::skinning(1, current_skin),
-                entry::skinning(6, prev_skin),
+                entry::skinning(1, current_skin.clone()),
+                entry::skinning(6, prev_skin.clone()),
             ],
         )
     }
@@ -405,7 +417,7 @@ impl MeshLayouts {
         &self,
         render_device: &RenderDevice,
         model: &BindingResource,
-        current_skin: &Buffer,
+        current_skin: &BindingResource,
         current_weights: &Buffer,
         targets: &TextureView,
     ) -> BindGroup {
@@ -414,7 +426,7 @@ impl MeshLayouts {
             &self.morphed_skinned,
             &[
                 entry::model(0, model.clone()),
-                entry::skinning(1, current_skin),
+                entry::skinning(1, current_skin.clone()),
                 entry::weights(2, current_weights),
                 entry::targets(3, targets),
             ],
@@ -433,10 +445,10 @@ impl MeshLayouts {
         &self,
         render_device: &RenderDevice,
         model: &BindingResource,
-        current_skin: &Buffer,
+        current_skin: &BindingResource,
         current_weights: &Buffer,
         targets: &TextureView,
-        prev_skin: &Buffer,
+        prev_skin: &BindingResource,
         prev_weights: &Buffer,
     ) -> BindGroup {
         render_device.create_bind_group(
@@ -444,10 +456,10 @@ impl MeshLayouts {
             &self.morphed_skinned_motion,
             &[
                 entry::model(0, model.clone()),
-                entry::skinning(1, current_skin),
+                entry::skinning(1, current_skin.clone()),
                 entry::weights(2, current_weights),
                 entry::targets(3, targets),
-                entry::skinning(6, prev_skin),
+                entry::skinning(6, prev_skin.clone()),
                 entry::weights(7, prev_weights),
             ],
         )
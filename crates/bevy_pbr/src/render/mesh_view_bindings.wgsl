@@ -5,6 +5,7 @@
     view::View,
     globals::Globals,
 }
+#import bevy_core_pipeline::fog::Fog
 
 @group(0) @binding(0) var<uniform> view: View;
 @group(0) @binding(1) var<uniform> lights: types::Lights;
@@ -32,7 +33,7 @@
 #endif
 
 @group(0) @binding(9) var<uniform> globals: Globals;
-@group(0) @binding(10) var<uniform> fog: types::Fog;
+@group(0) @binding(10) var<uniform> fog: Fog;
 @group(0) @binding(11) var<uniform> light_probes: types::LightProbes;
 
 const VISIBILITY_RANGE_UNIFORM_BUFFER_SIZE: u32 = 64u;
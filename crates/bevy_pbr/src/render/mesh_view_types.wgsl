@@ -71,30 +71,18 @@ struct Lights {
 #endif
 };
 
-struct Fog {
-    base_color: vec4<f32>,
-    directional_light_color: vec4<f32>,
-    // `be` and `bi` are allocated differently depending on the fog mode
-    //
-    // For Linear Fog:
-    //     be.x = start, be.y = end
-    // For Exponential and ExponentialSquared Fog:
-    //     be.x = density
-    // For Atmospheric Fog:
-    //     be = per-channel extinction density
-    //     bi = per-channel inscattering density
-    be: vec3<f32>,
-    directional_light_exponent: f32,
-    bi: vec3<f32>,
-    mode: u32,
-}
-
-// Important: These must be kept in sync with `fog.rs`
+// The `Fog` struct is shared with the 2d pipelines, and defined in `bevy_core_pipeline::fog`.
+// These constants are kept for existing shaders.
+//
+// Important: These must be kept in sync with `bevy_core_pipeline::fog`
 const FOG_MODE_OFF: u32                   = 0u;
 const FOG_MODE_LINEAR: u32                = 1u;
 const FOG_MODE_EXPONENTIAL: u32           = 2u;
 const FOG_MODE_EXPONENTIAL_SQUARED: u32   = 3u;
 const FOG_MODE_ATMOSPHERIC: u32           = 4u;
+const FOG_MODE_EXPONENTIAL_HEIGHT: u32    = 5u;
+const FOG_MODE_LINEAR_HEIGHT: u32         = 6u;
+const FOG_MODE_AERIAL_PERSPECTIVE: u32    = 7u;
 
 #if AVAILABLE_STORAGE_BUFFER_BINDINGS >= 3
 struct ClusterableObjects {
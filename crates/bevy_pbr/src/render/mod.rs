@@ -13,4 +13,6 @@ pub use light::*;
 pub use mesh::*;
 pub use mesh_bindings::MeshLayouts;
 pub use mesh_view_bindings::*;
-pub use skin::{extract_skins, prepare_skins, SkinIndices, SkinUniforms, MAX_JOINTS};
+pub use skin::{
+    extract_skins, prepare_skins, skins_use_uniform_buffers, SkinIndices, SkinUniforms, MAX_JOINTS,
+};
@@ -169,6 +169,7 @@ fn pbr_input_from_standard_material(
 #endif // VERTEX_UVS
 
     pbr_input.material.flags = pbr_bindings::material.flags;
+    pbr_input.material.fog_influence = pbr_bindings::material.fog_influence;
 
     // NOTE: Unlit bit not set means == 0 is true, so the true case is if lit
     if ((pbr_bindings::material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_UNLIT_BIT) == 0u) {
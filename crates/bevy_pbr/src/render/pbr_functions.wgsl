@@ -15,6 +15,7 @@
     mesh_types::{MESH_FLAGS_SHADOW_RECEIVER_BIT, MESH_FLAGS_TRANSMITTED_SHADOW_RECEIVER_BIT},
 }
 #import bevy_render::maths::{E, powsafe}
+#import bevy_core_pipeline::fog::{Fog, FOG_MODE_OFF, FOG_MODE_AERIAL_PERSPECTIVE}
 
 #ifdef MESHLET_MESH_MATERIAL_PASS
 #import bevy_pbr::meshlet_visibility_buffer_resolve::VertexOutput
@@ -694,14 +695,15 @@ fn apply_pbr_lighting(
         // We reuse the `atmospheric_fog()` function here, as it's fundamentally
         // equivalent to the attenuation that takes place inside the material volume,
         // and will allow us to eventually hook up subsurface scattering more easily
-        var attenuation_fog: mesh_view_types::Fog;
+        var attenuation_fog: Fog;
         attenuation_fog.base_color.a = 1.0;
+        attenuation_fog.max_opacity = 1.0;
         attenuation_fog.be = pow(1.0 - in.material.attenuation_color.rgb, vec3<f32>(E)) / in.material.attenuation_distance;
         // TODO: Add the subsurface scattering factor below
         // attenuation_fog.bi = /* ... */
-        transmitted_light = bevy_pbr::fog::atmospheric_fog(
+        transmitted_light = bevy_core_pipeline::fog::atmospheric_fog(
             attenuation_fog, vec4<f32>(transmitted_light, 1.0), thickness,
-            vec3<f32>(0.0) // TODO: Pass in (pre-attenuated) scattered light contribution here
+            attenuation_fog.base_color // TODO: Pass in (pre-attenuated) scattered light contribution here
         ).rgb;
     }
 #endif
@@ -724,7 +726,7 @@ fn apply_pbr_lighting(
 }
 #endif // PREPASS_FRAGMENT
 
-fn apply_fog(fog_params: mesh_view_types::Fog, input_color: vec4<f32>, fragment_world_position: vec3<f32>, view_world_position: vec3<f32>) -> vec4<f32> {
+fn apply_fog(fog_params: Fog, input_color: vec4<f32>, fragment_world_position: vec3<f32>, view_world_position: vec3<f32>) -> vec4<f32> {
     let view_to_world = fragment_world_position.xyz - view_world_position.xyz;
 
     // `length()` is used here instead of just `view_to_world.z` since that produces more
@@ -749,17 +751,21 @@ fn apply_fog(fog_params: mesh_view_types::Fog, input_color: vec4<f32>, fragment_
         }
     }
 
-    if fog_params.mode == mesh_view_types::FOG_MODE_LINEAR {
-        return bevy_pbr::fog::linear_fog(fog_params, input_color, distance, scattering);
-    } else if fog_params.mode == mesh_view_types::FOG_MODE_EXPONENTIAL {
-        return bevy_pbr::fog::exponential_fog(fog_params, input_color, distance, scattering);
-    } else if fog_params.mode == mesh_view_types::FOG_MODE_EXPONENTIAL_SQUARED {
-        return bevy_pbr::fog::exponential_squared_fog(fog_params, input_color, distance, scattering);
-    } else if fog_params.mode == mesh_view_types::FOG_MODE_ATMOSPHERIC {
-        return bevy_pbr::fog::atmospheric_fog(fog_params, input_color, distance, scattering);
+    var fog_color: vec4<f32>;
+    if fog_params.mode == FOG_MODE_AERIAL_PERSPECTIVE {
+        fog_color = bevy_pbr::fog::aerial_perspective_fog_color(fog_params, view_world_position, fragment_world_position);
     } else {
-        return input_color;
+        fog_color = bevy_pbr::fog::scattering_adjusted_fog_color(fog_params, scattering);
     }
+
+    return bevy_core_pipeline::fog::apply_fog_color(
+        fog_params,
+        fog_color,
+        input_color,
+        bevy_core_pipeline::fog::fog_distance(fog_params, distance, view_world_position, fragment_world_position),
+        view_world_position,
+        fragment_world_position,
+    );
 }
 
 #ifdef PREMULTIPLY_ALPHA
@@ -823,8 +829,12 @@ fn main_pass_post_lighting_processing(
     var output_color = input_color;
 
     // fog
-    if (view_bindings::fog.mode != mesh_view_types::FOG_MODE_OFF && (pbr_input.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT) != 0u) {
-        output_color = apply_fog(view_bindings::fog, output_color, pbr_input.world_position.xyz, view_bindings::view.world_position.xyz);
+    if (view_bindings::fog.mode != FOG_MODE_OFF && (pbr_input.material.flags & pbr_types::STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT) != 0u) {
+        // The fog color's alpha modulates the whole fog effect, so scaling it applies the
+        // material's fog influence consistently across all falloff modes
+        var fog_params = view_bindings::fog;
+        fog_params.base_color.a *= pbr_input.material.fog_influence;
+        output_color = apply_fog(fog_params, output_color, pbr_input.world_position.xyz, view_bindings::view.world_position.xyz);
     }
 
 #ifdef TONEMAP_IN_SHADER
@@ -41,7 +41,12 @@ fn specular_transmissive_light(world_position: vec4<f32>, frag_coord: vec3<f32>,
         // If the material has zero roughness, we can use a faster approach without the blur
         background_color = fetch_transmissive_background_non_rough(offset_position, frag_coord);
     } else {
+#ifdef SCREEN_SPACE_SPECULAR_TRANSMISSION_MIP_CHAIN
+        // Sample a pre-blurred mip level instead of taking multiple jittered taps
+        background_color = fetch_transmissive_background_mip_chain(offset_position, frag_coord, view_z, perceptual_roughness);
+#else
         background_color = fetch_transmissive_background(offset_position, frag_coord, view_z, perceptual_roughness);
+#endif
     }
 
     // Compensate for exposure, since the background color is coming from an already exposure-adjusted texture
@@ -81,6 +86,43 @@ fn fetch_transmissive_background_non_rough(offset_position: vec2<f32>, frag_coor
     return background_color;
 }
 
+#ifdef SCREEN_SPACE_SPECULAR_TRANSMISSION_MIP_CHAIN
+fn fetch_transmissive_background_mip_chain(offset_position: vec2<f32>, frag_coord: vec3<f32>, view_z: f32, perceptual_roughness: f32) -> vec4<f32> {
+    // Same blur intensity as `fetch_transmissive_background()`, i.e. roughly the radius (in UV units)
+    // of the spiral of taps it would take
+    let blur_intensity = (perceptual_roughness * perceptual_roughness) / view_z;
+
+    // Pick the mip level whose texels are about as large as the blur's diameter. Each mip level
+    // was downsampled from the previous one, so it's already blurred accordingly.
+    // Note: Measured in texels of the transmission texture, which may be scaled down from the viewport
+    let blur_diameter_in_pixels = 2.0 * blur_intensity * f32(textureDimensions(view_bindings::view_transmission_texture).y);
+    let max_mip_level = f32(textureNumLevels(view_bindings::view_transmission_texture) - 1u);
+    let mip_level = clamp(log2(max(blur_diameter_in_pixels, 1.0)), 0.0, max_mip_level);
+
+    var background_color = textureSampleLevel(
+        view_bindings::view_transmission_texture,
+        view_bindings::view_transmission_sampler,
+        offset_position,
+        mip_level
+    );
+
+#ifdef DEPTH_PREPASS
+#ifndef WEBGL2
+    // Use depth prepass data to reject values that are in front of the current fragment
+    if prepass_utils::prepass_depth(vec4<f32>(offset_position * view_bindings::view.viewport.zw, 0.0, 0.0), 0u) > frag_coord.z {
+        background_color.a = 0.0;
+    }
+#endif
+#endif
+
+#ifdef TONEMAP_IN_SHADER
+    background_color = approximate_inverse_tone_mapping(background_color, view_bindings::view.color_grading);
+#endif
+
+    return background_color;
+}
+#endif
+
 fn fetch_transmissive_background(offset_position: vec2<f32>, frag_coord: vec3<f32>, view_z: f32, perceptual_roughness: f32) -> vec4<f32> {
     // Calculate view aspect ratio, used to scale offset so that it's proportionate
     let aspect = view_bindings::view.viewport.z / view_bindings::view.viewport.w;
@@ -103,10 +145,20 @@ fn fetch_transmissive_background(offset_position: vec2<f32>, frag_coord: vec3<f3
     let num_taps = 8; // Fallback to 8 taps, if not specified
 #endif
     let num_spirals = i32(ceil(f32(num_taps) / 8.0));
+#ifdef SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_SPIRAL_TURNS
+    let spiral_turns = f32(#{SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_SPIRAL_TURNS}); // Controlled by `ScreenSpaceTransmissionQuality::Custom`
+#else
+    let spiral_turns = 1.0;
+#endif
+#ifdef SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_JITTER_FIFTEENTHS
+    let fractional_jitter = f32(#{SCREEN_SPACE_SPECULAR_TRANSMISSION_BLUR_JITTER_FIFTEENTHS}) / 15.0; // Controlled by `ScreenSpaceTransmissionQuality::Custom`
+#else
+    let fractional_jitter = 1.0;
+#endif
 #ifdef TEMPORAL_JITTER
-    let random_angle = interleaved_gradient_noise(frag_coord.xy, view_bindings::globals.frame_count);
+    let random_angle = interleaved_gradient_noise(frag_coord.xy, view_bindings::globals.frame_count) * fractional_jitter;
 #else
-    let random_angle = interleaved_gradient_noise(frag_coord.xy, 0u);
+    let random_angle = interleaved_gradient_noise(frag_coord.xy, 0u) * fractional_jitter;
 #endif
     // Pixel checkerboard pattern (helps make the interleaved gradient noise pattern less visible)
     let pixel_checkboard = (
@@ -122,7 +174,7 @@ fn fetch_transmissive_background(offset_position: vec2<f32>, frag_coord: vec3<f3
     var result = vec4<f32>(0.0);
     for (var i: i32 = 0; i < num_taps; i = i + 1) {
         let current_spiral = (i >> 3u);
-        let angle = (random_angle + f32(current_spiral) / f32(num_spirals)) * 2.0 * PI;
+        let angle = (random_angle + spiral_turns * f32(current_spiral) / f32(num_spirals)) * 2.0 * PI;
         let m = vec2(sin(angle), cos(angle));
         let rotation_matrix = mat2x2(
             m.y, -m.x,
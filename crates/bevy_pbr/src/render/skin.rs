@@ -7,16 +7,30 @@ use bevy_math::Mat4;
 use bevy_render::{
     batching::NoAutomaticBatching,
     mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
-    render_resource::{BufferUsages, RawBufferVec},
+    render_resource::{
+        BindingResource, Buffer, BufferBinding, BufferSize, BufferUsages, RawBufferVec,
+    },
     renderer::{RenderDevice, RenderQueue},
     view::ViewVisibility,
     Extract,
 };
 use bevy_transform::prelude::GlobalTransform;
+use bevy_utils::warn_once;
 
-/// Maximum number of joints supported for skinned meshes.
+/// Maximum number of joints supported for skinned meshes, when joint matrices
+/// are stored in uniform buffers.
+///
+/// Storage buffers, used where supported, have no such limit. See
+/// [`skins_use_uniform_buffers`].
 pub const MAX_JOINTS: usize = 256;
 
+/// Returns true if joint matrices are stored in uniform buffers, limiting skinned
+/// meshes to [`MAX_JOINTS`] joints, because the platform doesn't support storage
+/// buffers (e.g. WebGL 2).
+pub fn skins_use_uniform_buffers(render_device: &RenderDevice) -> bool {
+    render_device.limits().max_storage_buffers_per_shader_stage == 0
+}
+
 #[derive(Component)]
 pub struct SkinIndex {
     pub index: u32,
@@ -62,17 +76,60 @@ pub struct SkinUniforms {
     pub current_buffer: RawBufferVec<Mat4>,
     /// Stores all the joint matrices for skinned meshes in the previous frame.
     pub prev_buffer: RawBufferVec<Mat4>,
+    /// The number of joint matrices visible through each binding of
+    /// [`SkinUniforms::current_buffer`].
+    pub current_binding_len: usize,
+    /// The number of joint matrices visible through each binding of
+    /// [`SkinUniforms::prev_buffer`].
+    pub prev_binding_len: usize,
+    /// Whether the buffers are uniform buffers rather than storage buffers. See
+    /// [`skins_use_uniform_buffers`].
+    pub use_uniform_buffers: bool,
 }
 
-impl Default for SkinUniforms {
-    fn default() -> Self {
+impl FromWorld for SkinUniforms {
+    fn from_world(world: &mut World) -> Self {
+        let use_uniform_buffers = skins_use_uniform_buffers(world.resource::<RenderDevice>());
+        let buffer_usages = if use_uniform_buffers {
+            BufferUsages::UNIFORM
+        } else {
+            BufferUsages::STORAGE
+        };
+
         Self {
-            current_buffer: RawBufferVec::new(BufferUsages::UNIFORM),
-            prev_buffer: RawBufferVec::new(BufferUsages::UNIFORM),
+            current_buffer: RawBufferVec::new(buffer_usages),
+            prev_buffer: RawBufferVec::new(buffer_usages),
+            current_binding_len: MAX_JOINTS,
+            prev_binding_len: MAX_JOINTS,
+            use_uniform_buffers,
         }
     }
 }
 
+impl SkinUniforms {
+    /// Returns the binding of the current frame's joint matrices, if any.
+    pub fn current_binding(&self) -> Option<BindingResource> {
+        self.current_buffer
+            .buffer()
+            .map(|buffer| joint_binding(buffer, self.current_binding_len))
+    }
+
+    /// Returns the binding of the previous frame's joint matrices, if any.
+    pub fn prev_binding(&self) -> Option<BindingResource> {
+        self.prev_buffer
+            .buffer()
+            .map(|buffer| joint_binding(buffer, self.prev_binding_len))
+    }
+}
+
+fn joint_binding(buffer: &Buffer, binding_len: usize) -> BindingResource {
+    BindingResource::Buffer(BufferBinding {
+        buffer,
+        offset: 0,
+        size: BufferSize::new((binding_len * size_of::<Mat4>()) as u64),
+    })
+}
+
 pub fn prepare_skins(
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
@@ -93,6 +150,11 @@ pub fn prepare_skins(
 }
 
 // Notes on implementation:
+// Where storage buffers are supported, the binding is a runtime-sized
+// array<mat4x4<f32>> instead, and N is the largest number of joints of any
+// skin this frame, so there's no limit on the number of joints. The rest of
+// this comment applies to both cases.
+//
 // We define the uniform binding as an array<mat4x4<f32>, N> in the shader,
 // where N is the maximum number of Mat4s we can fit in the uniform binding,
 // which may be as little as 16kB or 64kB. But, we may not need all N.
@@ -132,10 +194,19 @@ pub fn extract_skins(
     // purposes of motion vector computation.
     mem::swap(&mut skin_indices.current, &mut skin_indices.prev);
     mem::swap(&mut uniform.current_buffer, &mut uniform.prev_buffer);
+    mem::swap(
+        &mut uniform.current_binding_len,
+        &mut uniform.prev_binding_len,
+    );
     skin_indices.current.clear();
     uniform.current_buffer.clear();
 
     let mut last_start = 0;
+    let (max_joints, mut binding_len) = if uniform.use_uniform_buffers {
+        (MAX_JOINTS, MAX_JOINTS)
+    } else {
+        (usize::MAX, 1)
+    };
 
     // PERF: This can be expensive, can we move this to prepare?
     for (entity, view_visibility, skin) in &query {
@@ -148,12 +219,20 @@ pub fn extract_skins(
         };
         let start = buffer.len();
 
-        let target = start + skin.joints.len().min(MAX_JOINTS);
+        if skin.joints.len() > max_joints {
+            warn_once!(
+                "A skinned mesh has {} joints, but at most {MAX_JOINTS} are supported on this \
+                platform. The remaining joints will be ignored.",
+                skin.joints.len()
+            );
+        }
+
+        let target = start + skin.joints.len().min(max_joints);
         buffer.extend(
             joints
                 .iter_many(&skin.joints)
                 .zip(inverse_bindposes.iter())
-                .take(MAX_JOINTS)
+                .take(max_joints)
                 .map(|(joint, bindpose)| joint.affine() * *bindpose),
         );
         // iter_many will skip any failed fetches. This will cause it to assign the wrong bones,
@@ -163,6 +242,7 @@ pub fn extract_skins(
             continue;
         }
         last_start = last_start.max(start);
+        binding_len = binding_len.max(target - start);
 
         // Pad to 256 byte alignment
         while buffer.len() % 4 != 0 {
@@ -173,9 +253,10 @@ pub fn extract_skins(
     }
 
     // Pad out the buffer to ensure that there's enough space for bindings
-    while uniform.current_buffer.len() - last_start < MAX_JOINTS {
+    while uniform.current_buffer.len() - last_start < binding_len {
         uniform.current_buffer.push(Mat4::ZERO);
     }
+    uniform.current_binding_len = binding_len;
 }
 
 // NOTE: The skinned joints uniform buffer has to be bound at a dynamic offset per
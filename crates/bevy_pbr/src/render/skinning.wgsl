@@ -4,7 +4,12 @@
 
 #ifdef SKINNED
 
+#ifdef SKINS_USE_UNIFORM_BUFFERS
 @group(1) @binding(1) var<uniform> joint_matrices: SkinnedMesh;
+#else   // SKINS_USE_UNIFORM_BUFFERS
+// Storage buffers aren't limited to `MAX_JOINTS` joints.
+@group(1) @binding(1) var<storage> joint_matrices: array<mat4x4<f32>>;
+#endif  // SKINS_USE_UNIFORM_BUFFERS
 
 // An array of matrices specifying the joint positions from the previous frame.
 //
@@ -12,16 +17,36 @@
 //
 // If this is the first frame, or we're otherwise prevented from using data from
 // the previous frame, this is simply the same as `joint_matrices` above.
+#ifdef SKINS_USE_UNIFORM_BUFFERS
 @group(1) @binding(6) var<uniform> prev_joint_matrices: SkinnedMesh;
+#else   // SKINS_USE_UNIFORM_BUFFERS
+@group(1) @binding(6) var<storage> prev_joint_matrices: array<mat4x4<f32>>;
+#endif  // SKINS_USE_UNIFORM_BUFFERS
+
+fn joint_matrix(index: u32) -> mat4x4<f32> {
+#ifdef SKINS_USE_UNIFORM_BUFFERS
+    return joint_matrices.data[index];
+#else   // SKINS_USE_UNIFORM_BUFFERS
+    return joint_matrices[index];
+#endif  // SKINS_USE_UNIFORM_BUFFERS
+}
+
+fn prev_joint_matrix(index: u32) -> mat4x4<f32> {
+#ifdef SKINS_USE_UNIFORM_BUFFERS
+    return prev_joint_matrices.data[index];
+#else   // SKINS_USE_UNIFORM_BUFFERS
+    return prev_joint_matrices[index];
+#endif  // SKINS_USE_UNIFORM_BUFFERS
+}
 
 fn skin_model(
     indexes: vec4<u32>,
     weights: vec4<f32>,
 ) -> mat4x4<f32> {
-    return weights.x * joint_matrices.data[indexes.x]
-        + weights.y * joint_matrices.data[indexes.y]
-        + weights.z * joint_matrices.data[indexes.z]
-        + weights.w * joint_matrices.data[indexes.w];
+    return weights.x * joint_matrix(indexes.x)
+        + weights.y * joint_matrix(indexes.y)
+        + weights.z * joint_matrix(indexes.z)
+        + weights.w * joint_matrix(indexes.w);
 }
 
 // Returns the skinned position of a vertex with the given weights from the
@@ -32,10 +57,10 @@ fn skin_prev_model(
     indexes: vec4<u32>,
     weights: vec4<f32>,
 ) -> mat4x4<f32> {
-    return weights.x * prev_joint_matrices.data[indexes.x]
-        + weights.y * prev_joint_matrices.data[indexes.y]
-        + weights.z * prev_joint_matrices.data[indexes.z]
-        + weights.w * prev_joint_matrices.data[indexes.w];
+    return weights.x * prev_joint_matrix(indexes.x)
+        + weights.y * prev_joint_matrix(indexes.y)
+        + weights.z * prev_joint_matrix(indexes.z)
+        + weights.w * prev_joint_matrix(indexes.w);
 }
 
 fn inverse_transpose_3x3m(in: mat3x3<f32>) -> mat3x3<f32> {
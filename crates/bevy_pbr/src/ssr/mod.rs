@@ -5,6 +5,7 @@ use bevy_asset::{load_internal_asset, Handle};
 use bevy_core_pipeline::{
     core_3d::{
         graph::{Core3d, Node3d},
+        TransmissiveReflectionCapture, ViewTransmissiveReflectionCaptureTexture,
         DEPTH_TEXTURE_SAMPLING_SUPPORTED,
     },
     fullscreen_vertex_shader,
@@ -84,6 +85,11 @@ pub struct ScreenSpaceReflectionsBundle {
 /// [`crate::environment_map::ReflectionProbeBundle`]s. The advantage of SSR is
 /// that it can reflect all objects, not just static ones.
 ///
+/// Transmissive and transparent objects aren't part of the depth prepass, nor
+/// drawn yet when reflections are traced, so they don't appear in reflections.
+/// Add a [`TransmissiveReflectionCapture`] to the camera as well to reflect an
+/// approximate, one frame old version of them.
+///
 /// SSR is an approximation technique and produces artifacts in some situations.
 /// Hand-tuning the settings in this component will likely be useful.
 ///
@@ -187,6 +193,7 @@ pub struct ScreenSpaceReflectionsPipelineKey {
     mesh_pipeline_view_key: MeshPipelineViewLayoutKey,
     is_hdr: bool,
     has_environment_maps: bool,
+    has_transmissive_reflection_capture: bool,
 }
 
 impl Plugin for ScreenSpaceReflectionsPlugin {
@@ -266,6 +273,7 @@ impl ViewNode for ScreenSpaceReflectionsNode {
         Read<ViewEnvironmentMapUniformOffset>,
         Read<MeshViewBindGroup>,
         Read<ScreenSpaceReflectionsPipelineId>,
+        Option<Read<ViewTransmissiveReflectionCaptureTexture>>,
     );
 
     fn run<'w>(
@@ -282,6 +290,7 @@ impl ViewNode for ScreenSpaceReflectionsNode {
             view_environment_map_offset,
             view_bind_group,
             ssr_pipeline_id,
+            transmissive_reflection_capture,
         ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
@@ -294,6 +303,14 @@ impl ViewNode for ScreenSpaceReflectionsNode {
         // Set up a standard pair of postprocessing textures.
         let postprocess = view_target.post_process_write();
 
+        // Reflections blend in the previous frame's capture, if any, so that
+        // they include transmissive and transparent objects. Without one,
+        // the capture is the main texture itself, which blends in nothing.
+        let reflected_color_texture = match transmissive_reflection_capture {
+            Some(capture) if capture.has_previous_capture => &capture.texture.default_view,
+            _ => postprocess.source,
+        };
+
         // Create the bind group for this view.
         let ssr_pipeline = world.resource::<ScreenSpaceReflectionsPipeline>();
         let ssr_bind_group = render_context.render_device().create_bind_group(
@@ -304,6 +321,7 @@ impl ViewNode for ScreenSpaceReflectionsNode {
                 &ssr_pipeline.color_sampler,
                 &ssr_pipeline.depth_linear_sampler,
                 &ssr_pipeline.depth_nearest_sampler,
+                reflected_color_texture,
             )),
         );
 
@@ -358,6 +376,7 @@ impl FromWorld for ScreenSpaceReflectionsPipeline {
                     binding_types::sampler(SamplerBindingType::Filtering),
                     binding_types::sampler(SamplerBindingType::Filtering),
                     binding_types::sampler(SamplerBindingType::NonFiltering),
+                    binding_types::texture_2d(TextureSampleType::Float { filterable: true }),
                 ),
             ),
         );
@@ -415,6 +434,7 @@ pub fn prepare_ssr_pipelines(
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<NormalPrepass>,
             Has<MotionVectorPrepass>,
+            Has<TransmissiveReflectionCapture>,
         ),
         (
             With<ScreenSpaceReflectionsUniform>,
@@ -429,6 +449,7 @@ pub fn prepare_ssr_pipelines(
         has_environment_maps,
         has_normal_prepass,
         has_motion_vector_prepass,
+        has_transmissive_reflection_capture,
     ) in &views
     {
         // SSR is only supported in the deferred pipeline, which has no MSAA
@@ -453,6 +474,7 @@ pub fn prepare_ssr_pipelines(
                 mesh_pipeline_view_key,
                 is_hdr: extracted_view.hdr,
                 has_environment_maps,
+                has_transmissive_reflection_capture,
             },
         );
 
@@ -531,6 +553,10 @@ impl SpecializedRenderPipeline for ScreenSpaceReflectionsPipeline {
             shader_defs.push("MULTIPLE_LIGHT_PROBES_IN_ARRAY".into());
         }
 
+        if key.has_transmissive_reflection_capture {
+            shader_defs.push("TRANSMISSIVE_REFLECTION_CAPTURE".into());
+        }
+
         RenderPipelineDescriptor {
             label: Some("SSR pipeline".into()),
             layout: vec![mesh_view_layout.clone(), self.bind_group_layout.clone()],
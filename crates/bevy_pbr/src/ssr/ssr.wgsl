@@ -42,6 +42,17 @@
 
 // Group 1, bindings 2 and 3 are in `raymarch.wgsl`.
 
+#ifdef TRANSMISSIVE_REFLECTION_CAPTURE
+// A low resolution capture of the previous frame, including transmissive and
+// transparent objects, which aren't drawn yet in `color_texture`.
+@group(1) @binding(4) var transmissive_reflection_capture_texture: texture_2d<f32>;
+
+// The range of relative differences between the capture and `color_texture`
+// over which reflections fade from the latter to the former.
+const TRANSMISSIVE_REFLECTION_CAPTURE_MIN_DIFFERENCE: f32 = 0.1;
+const TRANSMISSIVE_REFLECTION_CAPTURE_MAX_DIFFERENCE: f32 = 0.25;
+#endif
+
 // Returns the reflected color in the RGB channel and the specular occlusion in
 // the alpha channel.
 //
@@ -71,10 +82,34 @@ fn evaluate_ssr(R_world: vec3<f32>, P_world: vec3<f32>) -> vec4<f32> {
 
     let raymarch_result = depth_ray_march_march(&raymarch);
     if (raymarch_result.hit) {
-        return vec4(
-            textureSampleLevel(color_texture, color_sampler, raymarch_result.hit_uv, 0.0).rgb,
+        var hit_color =
+            textureSampleLevel(color_texture, color_sampler, raymarch_result.hit_uv, 0.0).rgb;
+
+#ifdef TRANSMISSIVE_REFLECTION_CAPTURE
+        // The capture is low resolution and one frame old, so only use it
+        // where it noticeably differs from the current frame, i.e. where
+        // transmissive or transparent objects were drawn over opaque ones.
+        // Elsewhere, keep the sharp, up to date color.
+        let captured_color = textureSampleLevel(
+            transmissive_reflection_capture_texture,
+            color_sampler,
+            raymarch_result.hit_uv,
             0.0
+        ).rgb;
+        let difference = length(captured_color - hit_color) /
+            (length(captured_color) + length(hit_color) + 0.0001);
+        hit_color = mix(
+            hit_color,
+            captured_color,
+            smoothstep(
+                TRANSMISSIVE_REFLECTION_CAPTURE_MIN_DIFFERENCE,
+                TRANSMISSIVE_REFLECTION_CAPTURE_MAX_DIFFERENCE,
+                difference
+            )
         );
+#endif
+
+        return vec4(hit_color, 0.0);
     }
 
     return vec4(0.0, 0.0, 0.0, 1.0);
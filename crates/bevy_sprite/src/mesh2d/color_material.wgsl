@@ -1,4 +1,5 @@
 #import bevy_sprite::{
+    mesh2d_functions::mesh2d_apply_fog,
     mesh2d_vertex_output::VertexOutput,
     mesh2d_view_bindings::view,
 }
@@ -39,6 +40,7 @@ fn fragment(
     }
 
     output_color = alpha_discard(material, output_color);
+    output_color = mesh2d_apply_fog(output_color, mesh.world_position);
 
 #ifdef TONEMAP_IN_SHADER
     output_color = tonemapping::tone_mapping(output_color, view.color_grading);
@@ -3,6 +3,7 @@ use bevy_asset::{load_internal_asset, AssetId, Handle};
 
 use bevy_core_pipeline::{
     core_2d::{AlphaMask2d, Camera2d, Opaque2d, Transparent2d, CORE_2D_DEPTH_FORMAT},
+    fog::{FogMeta, GpuFog, ViewFogUniformOffset},
     tonemapping::{
         get_lut_bind_group_layout_entries, get_lut_bindings, Tonemapping, TonemappingLuts,
     },
@@ -295,6 +296,10 @@ impl FromWorld for Mesh2dPipeline {
                         3,
                         tonemapping_lut_entries[1].visibility(ShaderStages::FRAGMENT),
                     ),
+                    (
+                        4,
+                        uniform_buffer::<GpuFog>(true).visibility(ShaderStages::FRAGMENT),
+                    ),
                 ),
             ),
         );
@@ -735,13 +740,15 @@ pub fn prepare_mesh2d_view_bind_groups(
     view_uniforms: Res<ViewUniforms>,
     views: Query<(Entity, &Tonemapping), (With<ExtractedView>, With<Camera2d>)>,
     globals_buffer: Res<GlobalsBuffer>,
+    fog_meta: Res<FogMeta>,
     tonemapping_luts: Res<TonemappingLuts>,
     images: Res<RenderAssets<GpuImage>>,
     fallback_image: Res<FallbackImage>,
 ) {
-    let (Some(view_binding), Some(globals)) = (
+    let (Some(view_binding), Some(globals), Some(fog_binding)) = (
         view_uniforms.uniforms.binding(),
         globals_buffer.buffer.binding(),
+        fog_meta.gpu_fogs.binding(),
     ) else {
         return;
     };
@@ -757,6 +764,7 @@ pub fn prepare_mesh2d_view_bind_groups(
                 (1, globals.clone()),
                 (2, lut_bindings.0),
                 (3, lut_bindings.1),
+                (4, fog_binding.clone()),
             )),
         );
 
@@ -769,18 +777,26 @@ pub fn prepare_mesh2d_view_bind_groups(
 pub struct SetMesh2dViewBindGroup<const I: usize>;
 impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetMesh2dViewBindGroup<I> {
     type Param = ();
-    type ViewQuery = (Read<ViewUniformOffset>, Read<Mesh2dViewBindGroup>);
+    type ViewQuery = (
+        Read<ViewUniformOffset>,
+        Read<ViewFogUniformOffset>,
+        Read<Mesh2dViewBindGroup>,
+    );
     type ItemQuery = ();
 
     #[inline]
     fn render<'w>(
         _item: &P,
-        (view_uniform, mesh2d_view_bind_group): ROQueryItem<'w, Self::ViewQuery>,
+        (view_uniform, view_fog, mesh2d_view_bind_group): ROQueryItem<'w, Self::ViewQuery>,
         _view: Option<()>,
         _param: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        pass.set_bind_group(I, &mesh2d_view_bind_group.value, &[view_uniform.offset]);
+        pass.set_bind_group(
+            I,
+            &mesh2d_view_bind_group.value,
+            &[view_uniform.offset, view_fog.offset],
+        );
 
         RenderCommandResult::Success
     }
@@ -66,6 +66,9 @@ fn fragment(
 ) -> @location(0) vec4<f32> {
 #ifdef VERTEX_COLORS
     var color = in.color;
+#ifdef VERTEX_POSITIONS
+    color = mesh_functions::mesh2d_apply_fog(color, in.world_position);
+#endif
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
 #endif
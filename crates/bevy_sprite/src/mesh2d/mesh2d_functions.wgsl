@@ -1,10 +1,16 @@
 #define_import_path bevy_sprite::mesh2d_functions
 
 #import bevy_sprite::{
-    mesh2d_view_bindings::view,
+    mesh2d_view_bindings::{view, fog},
     mesh2d_bindings::mesh,
 }
 #import bevy_render::maths::{affine3_to_square, mat2x4_f32_to_mat3x3_unpack}
+#import bevy_core_pipeline::fog::{
+    apply_fog,
+    aerial_perspective_lut_sample,
+    FOG_MODE_OFF,
+    FOG_MODE_AERIAL_PERSPECTIVE,
+}
 
 fn get_world_from_local(instance_index: u32) -> mat4x4<f32> {
     return affine3_to_square(mesh[instance_index].world_from_local);
@@ -43,3 +49,35 @@ fn mesh2d_tangent_local_to_world(world_from_local: mat4x4<f32>, vertex_tangent:
         vertex_tangent.w
     );
 }
+
+// Applies the view's `DistanceFog`, if any. In 2d, the fog distance is the view space depth of
+// the fragment, so that layers at the same depth are fogged equally across the screen.
+fn mesh2d_apply_fog(input_color: vec4<f32>, world_position: vec4<f32>) -> vec4<f32> {
+    if fog.mode == FOG_MODE_OFF {
+        return input_color;
+    }
+
+    var fog_color = fog.base_color;
+    if fog.mode == FOG_MODE_AERIAL_PERSPECTIVE {
+        // Indexes the lookup table from the view binding, see `aerial_perspective_lut_sample()`
+        let lut_sample = aerial_perspective_lut_sample(fog, view.world_position, world_position.xyz);
+        fog_color = vec4<f32>(
+            mix(
+                fog.aerial_perspective_lut[lut_sample.index].rgb,
+                fog.aerial_perspective_lut[lut_sample.index + 1u].rgb,
+                lut_sample.blend,
+            ),
+            fog.base_color.a,
+        );
+    }
+
+    let view_position = view.view_from_world * world_position;
+    return apply_fog(
+        fog,
+        fog_color,
+        input_color,
+        max(-view_position.z, 0.0),
+        view.world_position,
+        world_position.xyz,
+    );
+}
@@ -2,6 +2,7 @@
 
 #import bevy_render::view::View
 #import bevy_render::globals::Globals
+#import bevy_core_pipeline::fog::Fog
 
 @group(0) @binding(0) var<uniform> view: View;
 
@@ -9,3 +10,5 @@
 
 @group(0) @binding(2) var dt_lut_texture: texture_3d<f32>;
 @group(0) @binding(3) var dt_lut_sampler: sampler;
+
+@group(0) @binding(4) var<uniform> fog: Fog;
@@ -8,6 +8,7 @@ use bevy_asset::{AssetEvent, AssetId, Assets, Handle};
 use bevy_color::{ColorToComponents, LinearRgba};
 use bevy_core_pipeline::{
     core_2d::{Transparent2d, CORE_2D_DEPTH_FORMAT},
+    fog::{FogMeta, GpuFog, ViewFogUniformOffset},
     tonemapping::{
         get_lut_bind_group_layout_entries, get_lut_bindings, DebandDither, Tonemapping,
         TonemappingLuts,
@@ -76,6 +77,10 @@ impl FromWorld for SpritePipeline {
                         2,
                         tonemapping_lut_entries[1].visibility(ShaderStages::FRAGMENT),
                     ),
+                    (
+                        3,
+                        uniform_buffer::<GpuFog>(true).visibility(ShaderStages::FRAGMENT),
+                    ),
                 ),
             ),
         );
@@ -593,11 +598,14 @@ pub fn prepare_sprite_view_bind_groups(
     sprite_pipeline: Res<SpritePipeline>,
     view_uniforms: Res<ViewUniforms>,
     views: Query<(Entity, &Tonemapping), With<ExtractedView>>,
+    fog_meta: Res<FogMeta>,
     tonemapping_luts: Res<TonemappingLuts>,
     images: Res<RenderAssets<GpuImage>>,
     fallback_image: Res<FallbackImage>,
 ) {
-    let Some(view_binding) = view_uniforms.uniforms.binding() else {
+    let (Some(view_binding), Some(fog_binding)) =
+        (view_uniforms.uniforms.binding(), fog_meta.gpu_fogs.binding())
+    else {
         return;
     };
 
@@ -611,6 +619,7 @@ pub fn prepare_sprite_view_bind_groups(
                 (0, view_binding.clone()),
                 (1, lut_bindings.0),
                 (2, lut_bindings.1),
+                (3, fog_binding.clone()),
             )),
         );
 
@@ -809,17 +818,25 @@ pub type DrawSprite = (
 pub struct SetSpriteViewBindGroup<const I: usize>;
 impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetSpriteViewBindGroup<I> {
     type Param = ();
-    type ViewQuery = (Read<ViewUniformOffset>, Read<SpriteViewBindGroup>);
+    type ViewQuery = (
+        Read<ViewUniformOffset>,
+        Read<ViewFogUniformOffset>,
+        Read<SpriteViewBindGroup>,
+    );
     type ItemQuery = ();
 
     fn render<'w>(
         _item: &P,
-        (view_uniform, sprite_view_bind_group): ROQueryItem<'w, Self::ViewQuery>,
+        (view_uniform, view_fog, sprite_view_bind_group): ROQueryItem<'w, Self::ViewQuery>,
         _entity: Option<()>,
         _param: SystemParamItem<'w, '_, Self::Param>,
         pass: &mut TrackedRenderPass<'w>,
     ) -> RenderCommandResult {
-        pass.set_bind_group(I, &sprite_view_bind_group.value, &[view_uniform.offset]);
+        pass.set_bind_group(
+            I,
+            &sprite_view_bind_group.value,
+            &[view_uniform.offset, view_fog.offset],
+        );
         RenderCommandResult::Success
     }
 }
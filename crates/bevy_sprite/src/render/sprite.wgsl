@@ -6,8 +6,14 @@
     maths::affine3_to_square,
     view::View,
 }
+#import bevy_core_pipeline::fog::{
+    apply_fog,
+    aerial_perspective_lut_sample,
+    FOG_MODE_OFF,
+    FOG_MODE_AERIAL_PERSPECTIVE,
+}
 
-#import bevy_sprite::sprite_view_bindings::view
+#import bevy_sprite::sprite_view_bindings::{view, fog}
 
 struct VertexInput {
     @builtin(vertex_index) index: u32,
@@ -27,6 +33,7 @@ struct VertexOutput {
     @location(0) uv: vec2<f32>,
     @location(1) @interpolate(flat) color: vec4<f32>,
     @location(2) @interpolate(flat) effects: vec4<f32>,
+    @location(3) world_position: vec4<f32>,
 };
 
 @vertex
@@ -39,11 +46,12 @@ fn vertex(in: VertexInput) -> VertexOutput {
         0.0
     );
 
-    out.clip_position = view.clip_from_world * affine3_to_square(mat3x4<f32>(
+    out.world_position = affine3_to_square(mat3x4<f32>(
         in.i_model_transpose_col0,
         in.i_model_transpose_col1,
         in.i_model_transpose_col2,
     )) * vec4<f32>(vertex_position, 1.0);
+    out.clip_position = view.clip_from_world * out.world_position;
     out.uv = vec2<f32>(vertex_position.xy) * in.i_uv_offset_scale.zw + in.i_uv_offset_scale.xy;
     out.color = in.i_color;
     out.effects = in.i_effects;
@@ -66,6 +74,34 @@ fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
     color = vec4(mix(color.rgb, vec3(0.0, 0.0, 0.0), max(shadow - highlight, 0.0) * in.effects.x), color.a);
     color = vec4(mix(color.rgb, color.rgb * 2.0, max(highlight, 0.0) * in.effects.x), color.a);
 
+    // In 2d, the fog distance is the view space depth of the sprite. It includes the distance
+    // from the camera to the scene, see `DistanceFog`
+    if fog.mode != FOG_MODE_OFF {
+        var fog_color = fog.base_color;
+        if fog.mode == FOG_MODE_AERIAL_PERSPECTIVE {
+            // Indexes the lookup table from the view binding, see `aerial_perspective_lut_sample()`
+            let lut_sample = aerial_perspective_lut_sample(fog, view.world_position, in.world_position.xyz);
+            fog_color = vec4<f32>(
+                mix(
+                    fog.aerial_perspective_lut[lut_sample.index].rgb,
+                    fog.aerial_perspective_lut[lut_sample.index + 1u].rgb,
+                    lut_sample.blend,
+                ),
+                fog.base_color.a,
+            );
+        }
+
+        let view_position = view.view_from_world * in.world_position;
+        color = apply_fog(
+            fog,
+            fog_color,
+            color,
+            max(-view_position.z, 0.0),
+            view.world_position,
+            in.world_position.xyz,
+        );
+    }
+
 #ifdef TONEMAP_IN_SHADER
     color = tonemapping::tone_mapping(color, view.color_grading);
 #endif
@@ -39,6 +39,7 @@ fn setup_camera_fog(mut commands: Commands) {
                 Color::srgb(0.35, 0.5, 0.66), // atmospheric extinction color (after light is lost due to absorption by atmospheric particles)
                 Color::srgb(0.8, 0.844, 1.0), // atmospheric inscattering color (light gained due to scattering from the sun)
             ),
+            ..default()
         },
     ));
 }
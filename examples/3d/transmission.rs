@@ -12,6 +12,8 @@
 //! | `Z` / `X`          | Decrease / Increase IOR                              |
 //! | `E` / `R`          | Decrease / Increase Perceptual Roughness             |
 //! | `U` / `I`          | Decrease / Increase Reflectance                      |
+//! | `F` / `G`          | Decrease / Increase Environment Map Intensity        |
+//! | `V` / `B`          | Rotate Environment Map                               |
 //! | Arrow Keys         | Control Camera                                       |
 //! | `C`                | Randomize Colors                                     |
 //! | `H`                | Toggle HDR + Bloom                                   |
@@ -429,6 +431,7 @@ fn example_control_system(
             &mut Camera,
             &mut Camera3d,
             &mut Transform,
+            &mut EnvironmentMapLight,
             Option<&DepthPrepass>,
             Option<&TemporalJitter>,
         ),
@@ -502,10 +505,25 @@ fn example_control_system(
         mut camera,
         mut camera_3d,
         mut camera_transform,
+        mut environment_map_light,
         depth_prepass,
         temporal_jitter,
     ) = camera.single_mut();
 
+    if input.pressed(KeyCode::KeyG) {
+        environment_map_light.intensity =
+            (environment_map_light.intensity + time.delta_seconds() * 25.0).min(100.0);
+    } else if input.pressed(KeyCode::KeyF) {
+        environment_map_light.intensity =
+            (environment_map_light.intensity - time.delta_seconds() * 25.0).max(0.0);
+    }
+
+    if input.pressed(KeyCode::KeyB) {
+        environment_map_light.rotation *= Quat::from_rotation_y(time.delta_seconds());
+    } else if input.pressed(KeyCode::KeyV) {
+        environment_map_light.rotation *= Quat::from_rotation_y(-time.delta_seconds());
+    }
+
     if input.just_pressed(KeyCode::KeyH) {
         camera.hdr = !camera.hdr;
     }
@@ -597,6 +615,8 @@ fn example_control_system(
             "         Z / X  IOR: {:.2}\n",
             "         E / R  Perceptual Roughness: {:.2}\n",
             "         U / I  Reflectance: {:.2}\n",
+            "         F / G  Environment Map Intensity: {:.1}\n",
+            "         V / B  Rotate Environment Map\n",
             "    Arrow Keys  Control Camera\n",
             "             C  Randomize Colors\n",
             "             H  HDR + Bloom: {}\n",
@@ -611,6 +631,7 @@ fn example_control_system(
         state.ior,
         state.perceptual_roughness,
         state.reflectance,
+        environment_map_light.intensity,
         if camera.hdr { "ON " } else { "OFF" },
         if cfg!(any(not(feature = "webgl2"), not(target_arch = "wasm32"))) {
             if depth_prepass.is_some() {